@@ -0,0 +1,32 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use game_of_life::domain::rules::next_generation_per_cell;
+use game_of_life::domain::{next_generation, Bounds, Grid, Rule, Soup, Stepper, Topology};
+
+/// Compares the bit-packed stepper, serial and parallel, with the per-cell
+/// reference on random soups of increasing size.
+fn step(c: &mut Criterion) {
+    let rule = Rule::conway();
+    let mut group = c.benchmark_group("step");
+    for size in [64, 200, 512, 2048] {
+        let mut grid = Grid::new(size, size);
+        Soup {
+            seed: 1,
+            ..Soup::default()
+        }
+        .fill(&mut grid, Bounds::new(0, 0, size as i64, size as i64));
+
+        group.bench_with_input(BenchmarkId::new("bit-packed", size), &grid, |b, grid| {
+            b.iter(|| next_generation(black_box(grid), &rule, Topology::Torus))
+        });
+        group.bench_with_input(BenchmarkId::new("parallel", size), &grid, |b, grid| {
+            b.iter(|| Stepper::Parallel.step(black_box(grid), &rule, Topology::Torus))
+        });
+        group.bench_with_input(BenchmarkId::new("per-cell", size), &grid, |b, grid| {
+            b.iter(|| next_generation_per_cell(black_box(grid), &rule, Topology::Torus))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, step);
+criterion_main!(benches);
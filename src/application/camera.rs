@@ -0,0 +1,101 @@
+use eframe::egui::{pos2, vec2, Pos2, Vec2};
+
+use crate::domain::{Bounds, MAX_GRID_SIZE};
+
+/// Smallest zoom, in pixels per cell; enough to fit a 4096-cell board.
+pub const MIN_SCALE: f32 = 0.05;
+/// Largest zoom, in pixels per cell.
+pub const MAX_SCALE: f32 = 64.0;
+
+/// Maps board cells to canvas pixels. Positions are relative to the canvas'
+/// top-left corner.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Camera {
+    /// Board coordinate, in fractional cells, shown at the canvas' top-left.
+    pub offset: Vec2,
+    /// Pixels per cell.
+    pub scale: f32,
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Self {
+            offset: Vec2::ZERO,
+            scale: 4.0,
+        }
+    }
+}
+
+impl Camera {
+    /// Zooms so `bounds` fills a canvas of `size` and centres it.
+    pub fn fit(&mut self, bounds: Bounds, size: Vec2) {
+        let width = bounds.width().max(1) as f32;
+        let height = bounds.height().max(1) as f32;
+        let scale = (size.x / width).min(size.y / height);
+        self.scale = if scale.is_finite() && scale > 0.0 {
+            scale.clamp(MIN_SCALE, MAX_SCALE)
+        } else {
+            Self::default().scale
+        };
+        self.center_on(bounds, size);
+    }
+
+    /// Moves, without zooming, so `bounds` is in the middle of the canvas.
+    pub fn center_on(&mut self, bounds: Bounds, size: Vec2) {
+        let center = vec2(
+            (bounds.left + bounds.right) as f32 / 2.0,
+            (bounds.top + bounds.bottom) as f32 / 2.0,
+        );
+        self.offset = center - size / (2.0 * self.scale);
+    }
+
+    /// Multiplies the zoom by `factor`, keeping the cell under `anchor` still.
+    pub fn zoom_at(&mut self, factor: f32, anchor: Pos2) {
+        let anchored = self.offset + anchor.to_vec2() / self.scale;
+        self.scale = (self.scale * factor).clamp(MIN_SCALE, MAX_SCALE);
+        self.offset = anchored - anchor.to_vec2() / self.scale;
+    }
+
+    /// Drags the board by `delta` pixels.
+    pub fn pan(&mut self, delta: Vec2) {
+        self.offset -= delta / self.scale;
+    }
+
+    pub fn screen_to_cell(&self, position: Pos2) -> (i64, i64) {
+        let cell = self.offset + position.to_vec2() / self.scale;
+        (cell.x.floor() as i64, cell.y.floor() as i64)
+    }
+
+    /// Top-left corner of cell `(x, y)`.
+    pub fn cell_to_screen(&self, x: i64, y: i64) -> Pos2 {
+        pos2(
+            (x as f32 - self.offset.x) * self.scale,
+            (y as f32 - self.offset.y) * self.scale,
+        )
+    }
+
+    /// Part of `view` to rasterise into a texture: all of it while it is no
+    /// larger than the biggest bounded grid, so panning reuses the texture, and
+    /// otherwise only the cells on screen. `None` when nothing is on screen or
+    /// the region would exceed `max_side` texels on a side.
+    pub fn texture_region(&self, size: Vec2, view: Bounds, max_side: usize) -> Option<Bounds> {
+        let limit = MAX_GRID_SIZE.min(max_side) as i64;
+        if view.width() <= limit && view.height() <= limit {
+            return self.visible(size).intersection(&view).map(|_| view);
+        }
+        let region = self.visible(size).intersection(&view)?;
+        let max_side = max_side as i64;
+        (region.width() <= max_side && region.height() <= max_side).then_some(region)
+    }
+
+    /// Cells at least partly visible on a canvas of `size`.
+    pub fn visible(&self, size: Vec2) -> Bounds {
+        let (left, top) = self.screen_to_cell(Pos2::ZERO);
+        let (right, bottom) = self.screen_to_cell(size.to_pos2());
+        Bounds::new(left, top, right - left + 1, bottom - top + 1)
+    }
+}
+
+#[path = "camera_test.rs"]
+#[cfg(test)]
+mod camera_test;
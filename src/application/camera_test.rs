@@ -0,0 +1,114 @@
+use eframe::egui::{pos2, vec2};
+
+use super::{Camera, MAX_SCALE, MIN_SCALE};
+use crate::domain::Bounds;
+
+#[test]
+fn screen_and_cell_coordinates_round_trip() {
+    let camera = Camera {
+        offset: vec2(-10.5, 3.0),
+        scale: 8.0,
+    };
+
+    assert_eq!(camera.screen_to_cell(pos2(0.0, 0.0)), (-11, 3));
+    assert_eq!(camera.screen_to_cell(pos2(4.0, 7.9)), (-10, 3));
+    assert_eq!(camera.cell_to_screen(-10, 4), pos2(4.0, 8.0));
+    assert_eq!(
+        camera.screen_to_cell(camera.cell_to_screen(25, -7)),
+        (25, -7)
+    );
+}
+
+#[test]
+fn fit_centres_the_bounds_at_the_largest_scale_that_fits() {
+    let mut camera = Camera::default();
+
+    camera.fit(Bounds::new(10, 20, 50, 25), vec2(400.0, 100.0));
+
+    assert_eq!(camera.scale, 4.0);
+    assert_eq!(camera.cell_to_screen(10, 20), pos2(100.0, 0.0));
+    assert_eq!(camera.cell_to_screen(60, 45), pos2(300.0, 100.0));
+}
+
+#[test]
+fn zoom_keeps_the_anchor_cell_under_the_cursor() {
+    let mut camera = Camera {
+        offset: vec2(0.0, 0.0),
+        scale: 4.0,
+    };
+    let anchor = pos2(122.0, 38.0);
+    let before = camera.screen_to_cell(anchor);
+
+    camera.zoom_at(2.5, anchor);
+
+    assert_eq!(camera.scale, 10.0);
+    assert_eq!(camera.screen_to_cell(anchor), before);
+}
+
+#[test]
+fn zoom_is_clamped() {
+    let mut camera = Camera::default();
+
+    camera.zoom_at(1000.0, pos2(0.0, 0.0));
+    assert_eq!(camera.scale, MAX_SCALE);
+    camera.zoom_at(0.0, pos2(0.0, 0.0));
+    assert_eq!(camera.scale, MIN_SCALE);
+}
+
+#[test]
+fn pan_moves_the_board_with_the_pointer() {
+    let mut camera = Camera {
+        offset: vec2(0.0, 0.0),
+        scale: 10.0,
+    };
+
+    camera.pan(vec2(30.0, -20.0));
+
+    assert_eq!(camera.screen_to_cell(pos2(30.0, 0.0)), (0, 2));
+}
+
+#[test]
+fn visible_covers_partly_shown_cells() {
+    let camera = Camera {
+        offset: vec2(-0.5, 0.0),
+        scale: 10.0,
+    };
+
+    assert_eq!(camera.visible(vec2(100.0, 50.0)), Bounds::new(-1, 0, 11, 6));
+}
+
+#[test]
+fn texture_region_covers_small_views_and_the_visible_part_of_large_ones() {
+    let camera = Camera {
+        offset: vec2(1000.0, 2000.0),
+        scale: 2.0,
+    };
+    let size = vec2(200.0, 100.0);
+
+    let small = Bounds::new(900, 1950, 300, 100);
+    assert_eq!(camera.texture_region(size, small, 8192), Some(small));
+
+    let huge = Bounds::new(-50_000, -50_000, 100_000, 100_000);
+    assert_eq!(
+        camera.texture_region(size, huge, 8192),
+        Some(Bounds::new(1000, 2000, 101, 51))
+    );
+
+    let offscreen = Bounds::new(0, 0, 10, 10);
+    assert_eq!(camera.texture_region(size, offscreen, 8192), None);
+}
+
+#[test]
+fn texture_region_refuses_more_than_the_texture_limit() {
+    let camera = Camera {
+        offset: vec2(0.0, 0.0),
+        scale: MIN_SCALE,
+    };
+    let huge = Bounds::new(0, 0, 1 << 40, 1 << 40);
+
+    assert_eq!(camera.texture_region(vec2(1000.0, 500.0), huge, 8192), None);
+    assert_eq!(
+        camera.texture_region(vec2(1000.0, 500.0), huge, 1 << 20),
+        Some(Bounds::new(0, 0, 20_001, 10_001))
+    );
+}
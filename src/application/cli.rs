@@ -0,0 +1,210 @@
+use std::fmt;
+use std::fs;
+use std::process::ExitCode;
+
+use crate::domain::{
+    parse_any, Board, Cell, Grid, PatternFile, PatternFormat, Rule, Stepper, Topology,
+    MAX_GRID_SIZE, STEPPERS, TOPOLOGIES,
+};
+
+/// Dead cells added around the pattern on each side when `--size` is omitted.
+const DEFAULT_MARGIN: usize = 32;
+
+const USAGE: &str = "\
+usage: game_of_life run <pattern-file> [options]
+
+options:
+  -n, --generations <N>   generations to run (default 0)
+  -r, --rule <RULE>       override the pattern's rule, e.g. B36/S23
+  -t, --topology <EDGES>  bounded, torus, klein-bottle, cross-surface or alive-border
+  -s, --size <WxH>        grid size, at most 4096x4096 (default: pattern plus a 32-cell margin)
+  -f, --format <FORMAT>   output as rle (default) or plaintext
+  -p, --stepper <KIND>    serial (default) or parallel, which spreads rows over every core";
+
+/// Settings for a headless `run`, parsed from the command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunOptions {
+    pub path: String,
+    pub generations: u64,
+    pub rule: Option<Rule>,
+    pub topology: Topology,
+    pub size: Option<(usize, usize)>,
+    pub format: PatternFormat,
+    pub stepper: Stepper,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CliError {
+    Usage(String),
+    Io(String),
+    Pattern(String),
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Usage(message) => write!(f, "{message}\n\n{USAGE}"),
+            CliError::Io(message) | CliError::Pattern(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+impl CliError {
+    /// 2 for bad arguments, 1 for files that cannot be read or parsed.
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            CliError::Usage(_) => 2,
+            CliError::Io(_) | CliError::Pattern(_) => 1,
+        }
+    }
+}
+
+/// Entry point for `game_of_life run …`; `args` excludes the subcommand.
+pub fn main(args: &[String]) -> ExitCode {
+    let result = parse_args(args).and_then(|options| {
+        let text = fs::read_to_string(&options.path)
+            .map_err(|err| CliError::Io(format!("could not read {}: {err}", options.path)))?;
+        run(&options, &text)
+    });
+
+    match result {
+        Ok(output) => {
+            print!("{output}");
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::from(err.exit_code())
+        }
+    }
+}
+
+pub fn parse_args(args: &[String]) -> Result<RunOptions, CliError> {
+    let mut path = None;
+    let mut options = RunOptions {
+        path: String::new(),
+        generations: 0,
+        rule: None,
+        topology: Topology::Bounded,
+        size: None,
+        format: PatternFormat::Rle,
+        stepper: Stepper::Serial,
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if !arg.starts_with('-') {
+            if path.replace(arg.clone()).is_some() {
+                return Err(CliError::Usage(format!("unexpected argument '{arg}'")));
+            }
+            continue;
+        }
+
+        let value = args
+            .next()
+            .ok_or_else(|| CliError::Usage(format!("missing value for '{arg}'")))?;
+        let invalid = || CliError::Usage(format!("invalid value '{value}' for '{arg}'"));
+        match arg.as_str() {
+            "-n" | "--generations" => options.generations = value.parse().map_err(|_| invalid())?,
+            "-r" | "--rule" => {
+                let rule = Rule::parse(value)
+                    .map_err(|err| CliError::Usage(format!("invalid rule '{value}': {err}")))?;
+                options.rule = Some(rule);
+            }
+            "-t" | "--topology" => options.topology = parse_topology(value).ok_or_else(invalid)?,
+            "-s" | "--size" => options.size = Some(parse_size(value).ok_or_else(invalid)?),
+            "-f" | "--format" => {
+                options.format = match value.to_ascii_lowercase().as_str() {
+                    "rle" => PatternFormat::Rle,
+                    "plaintext" | "cells" => PatternFormat::Plaintext,
+                    _ => return Err(invalid()),
+                }
+            }
+            "-p" | "--stepper" => {
+                options.stepper = STEPPERS
+                    .into_iter()
+                    .find(|stepper| stepper.name().eq_ignore_ascii_case(value))
+                    .ok_or_else(invalid)?
+            }
+            _ => return Err(CliError::Usage(format!("unknown option '{arg}'"))),
+        }
+    }
+
+    options.path = path.ok_or_else(|| CliError::Usage("missing pattern file".to_string()))?;
+    Ok(options)
+}
+
+/// Evolves the pattern in `text` and returns the final pattern, with the
+/// generation and population recorded as comments.
+pub fn run(options: &RunOptions, text: &str) -> Result<String, CliError> {
+    let pattern = parse_any(text).map_err(|err| CliError::Pattern(err.to_string()))?;
+    let rule = options.rule.or(pattern.rule).unwrap_or_default();
+
+    let (pattern_width, pattern_height) = pattern.size();
+    let (pattern_width, pattern_height) = (pattern_width as usize, pattern_height as usize);
+    let (width, height) = options.size.unwrap_or((
+        pattern_width + 2 * DEFAULT_MARGIN,
+        pattern_height + 2 * DEFAULT_MARGIN,
+    ));
+    if width > MAX_GRID_SIZE || height > MAX_GRID_SIZE {
+        return Err(CliError::Pattern(format!(
+            "pattern is {pattern_width} x {pattern_height} cells but grids are at most \
+             {MAX_GRID_SIZE} x {MAX_GRID_SIZE}"
+        )));
+    }
+    if pattern_width > width || pattern_height > height {
+        return Err(CliError::Pattern(format!(
+            "pattern is {pattern_width} x {pattern_height} cells but the grid is only \
+             {width} x {height}"
+        )));
+    }
+
+    let mut grid = Grid::new(width, height);
+    let left = (width - pattern_width) / 2;
+    let top = (height - pattern_height) / 2;
+    for &(x, y) in &pattern.cells {
+        grid.set(left + x as usize, top + y as usize, Cell::Alive);
+    }
+
+    for _ in 0..options.generations {
+        grid = options.stepper.step(&grid, &rule, options.topology);
+    }
+
+    let mut result =
+        PatternFile::from_board(&grid, rule).map_err(|err| CliError::Pattern(err.to_string()))?;
+    result.name = pattern.name;
+    result.comments.push(format!(
+        "Generation {}, population {}",
+        options.generations,
+        grid.population()
+    ));
+    options
+        .format
+        .write(&result)
+        .map_err(|err| CliError::Pattern(err.to_string()))
+}
+
+/// Accepts the topology's display name in lower case with `-` for spaces.
+fn parse_topology(value: &str) -> Option<Topology> {
+    let value = value.to_ascii_lowercase();
+    TOPOLOGIES
+        .into_iter()
+        .find(|topology| topology.name().to_ascii_lowercase().replace(' ', "-") == value)
+}
+
+/// Accepts `WxH` with both sides between 1 and `MAX_GRID_SIZE`.
+fn parse_size(value: &str) -> Option<(usize, usize)> {
+    let (width, height) = value
+        .to_ascii_lowercase()
+        .split_once('x')
+        .map(|(w, h)| (w.trim().parse::<usize>(), h.trim().parse::<usize>()))?;
+    let size = (width.ok()?, height.ok()?);
+    let valid = 1..=MAX_GRID_SIZE;
+    (valid.contains(&size.0) && valid.contains(&size.1)).then_some(size)
+}
+
+#[path = "cli_test.rs"]
+#[cfg(test)]
+mod cli_test;
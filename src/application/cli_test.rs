@@ -0,0 +1,121 @@
+use super::{parse_args, run, CliError, RunOptions};
+use crate::domain::{PatternFormat, Rule, Stepper, Topology};
+
+const BLINKER: &str = "#N Blinker\nx = 3, y = 1, rule = B3/S23\n3o!\n";
+
+fn args(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+fn options(generations: u64) -> RunOptions {
+    parse_args(&args(&["blinker.rle", "-n", &generations.to_string()])).unwrap()
+}
+
+#[test]
+fn parse_args_reads_every_option() {
+    let options = parse_args(&args(&[
+        "--rule",
+        "b36/s23",
+        "gun.rle",
+        "-n",
+        "120",
+        "--topology",
+        "klein-bottle",
+        "-s",
+        "80x60",
+        "-f",
+        "plaintext",
+        "--stepper",
+        "parallel",
+    ]))
+    .unwrap();
+
+    assert_eq!(
+        options,
+        RunOptions {
+            path: "gun.rle".to_string(),
+            generations: 120,
+            rule: Rule::parse("B36/S23").ok(),
+            topology: Topology::KleinBottle,
+            size: Some((80, 60)),
+            format: PatternFormat::Plaintext,
+            stepper: Stepper::Parallel,
+        }
+    );
+}
+
+#[test]
+fn parse_args_rejects_bad_arguments() {
+    for bad in [
+        &["-n", "5"][..],
+        &["a.rle", "b.rle"],
+        &["a.rle", "-n"],
+        &["a.rle", "-n", "many"],
+        &["a.rle", "--topology", "sphere"],
+        &["a.rle", "--size", "0x10"],
+        &["a.rle", "--size", "100000x100000"],
+        &["a.rle", "--size", "4097x10"],
+        &["a.rle", "--format", "mc"],
+        &["a.rle", "--rule", "B9"],
+        &["a.rle", "--verbose", "yes"],
+    ] {
+        assert!(
+            matches!(parse_args(&args(bad)), Err(CliError::Usage(_))),
+            "{bad:?} should be rejected"
+        );
+    }
+}
+
+#[test]
+fn run_evolves_pattern_and_reports_stats() {
+    let output = run(&options(1), BLINKER).unwrap();
+
+    assert_eq!(
+        output,
+        "#N Blinker\n#C Generation 1, population 3\nx = 1, y = 3, rule = B3/S23\no$o$o!\n"
+    );
+}
+
+#[test]
+fn run_writes_plaintext_and_applies_rule_override() {
+    let mut options = options(1);
+    options.format = PatternFormat::Plaintext;
+    options.rule = Rule::parse("B3/S").ok();
+
+    let output = run(&options, BLINKER).unwrap();
+
+    assert!(output.contains("!Generation 1, population 2\n"));
+    assert!(output.ends_with("O\n.\nO\n"));
+}
+
+#[test]
+fn run_respects_topology() {
+    let glider = "x = 3, y = 3\nbo$2bo$3o!\n";
+    let mut options = options(12);
+    options.size = Some((6, 6));
+
+    let bounded = run(&options, glider).unwrap();
+    options.topology = Topology::Torus;
+    let torus = run(&options, glider).unwrap();
+
+    assert!(!bounded.contains("population 5"));
+    assert!(torus.contains("population 5"));
+}
+
+#[test]
+fn run_reports_parse_and_size_errors() {
+    assert!(matches!(
+        run(&options(0), "x = 3, y = 1\n3q!\n"),
+        Err(CliError::Pattern(_))
+    ));
+
+    let mut options = options(0);
+    options.size = Some((2, 2));
+    assert!(matches!(run(&options, BLINKER), Err(CliError::Pattern(_))));
+
+    options.size = None;
+    assert!(matches!(
+        run(&options, "x = 0, y = 0\no5000bo!\n"),
+        Err(CliError::Pattern(_))
+    ));
+}
@@ -1,3 +1,6 @@
+use std::fs;
+use std::sync::mpsc;
+use std::thread;
 use std::time::{Duration, Instant};
 
 use eframe::egui::{
@@ -6,24 +9,177 @@ use eframe::egui::{
     Color32, ComboBox, RichText, Sense, Slider,
 };
 
+use super::camera::Camera;
+use super::history::History;
+use super::timeline::{Frame, Timeline};
 use crate::domain::{
-    next_generation, Cell, Grid, MAX_GRID_SIZE, MAX_UPS, MIN_GRID_SIZE, MIN_UPS, PATTERNS,
+    find_velocity, flood_fill, line, macrocell, next_generation_unbounded, parse_any, rle,
+    take_census, Board, Bounds, Cell, Census, Clip, CycleDetector, Figure, Grid, HashLife,
+    PasteMode, PatternFile, PatternFormat, Rule, Soup, SparseGrid, Stability, Stepper, Topology,
+    Transform, MAX_CELL_LIST_POPULATION, MAX_FILL_CELLS, MAX_GRID_SIZE, MAX_STEP_EXPONENT, MAX_UPS,
+    MIN_GRID_SIZE, MIN_UPS, PASTE_MODES, PATTERNS, PATTERN_FORMATS, RULE_PRESETS, STEPPERS,
+    SYMMETRIES, TOPOLOGIES, TRANSFORMS,
 };
 
+/// Zoom factor per point of mouse-wheel scrolling, as an exponent.
+const ZOOM_PER_SCROLL_POINT: f32 = 0.0025;
+/// Empty cells kept around the pattern when the unbounded view re-centres.
+const VIEW_MARGIN: i64 = 8;
+/// Approximate memory the undo history may hold before dropping old entries.
+const HISTORY_BUDGET_BYTES: usize = 64 * 1024 * 1024;
+/// Longest period "Analyze motion" looks for.
+const MAX_ANALYZED_PERIOD: u64 = 256;
+/// Largest batch "Advance" runs synchronously in one frame.
+const MAX_ADVANCE_COUNT: u64 = 10_000;
+/// Approximate memory kept for rewinding past generations.
+const TIMELINE_BUDGET_BYTES: usize = 32 * 1024 * 1024;
+
+/// What a primary-button drag on the canvas does; the secondary button
+/// erases with the drawing tools.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tool {
+    /// Freehand painting.
+    Draw,
+    /// Drags out a line, rectangle or ellipse.
+    Figure(Figure),
+    /// Fills the clicked region of dead or live cells.
+    Fill,
+    Select,
+    /// Places the selected library pattern at every click.
+    Stamp,
+}
+
+/// Palette entries with their button labels.
+const TOOLS: [(Tool, &str); 7] = [
+    (Tool::Draw, "Freehand (D)"),
+    (Tool::Figure(Figure::Line), "Line (L)"),
+    (Tool::Figure(Figure::Rectangle), "Rectangle (B)"),
+    (Tool::Figure(Figure::Ellipse), "Ellipse (E)"),
+    (Tool::Fill, "Fill (G)"),
+    (Tool::Select, "Select (S)"),
+    (Tool::Stamp, "Stamp (P)"),
+];
+
+/// What the canvas texture depends on; it is redrawn when any of it changes.
+type CanvasKey = (u64, Bounds, Color32, Color32);
+
+/// Board state saved before each undoable action.
+struct Snapshot {
+    grid: Grid,
+    plane: SparseGrid,
+    unbounded: bool,
+    plane_view: Bounds,
+    generation: u64,
+}
+
+impl Snapshot {
+    /// Rough heap size, used to keep the history within its budget.
+    fn cost(&self) -> usize {
+        self.grid.height() * self.grid.words_per_row() * std::mem::size_of::<u64>()
+            + self.plane.population() * std::mem::size_of::<(i64, i64)>() * 2
+    }
+}
+
 pub struct GameApp {
     grid: Grid,
+    /// Infinite plane used instead of `grid` while `unbounded` is set.
+    plane: SparseGrid,
+    unbounded: bool,
+    /// Region of `plane` shown on the canvas; follows the pattern as it grows.
+    plane_view: Bounds,
     generation: u64,
     paused: bool,
     last_step: Instant,
     settings: Settings,
     selected_pattern: usize,
+    /// Offsets of the selected library pattern, as rotated and flipped so far.
+    pattern_cells: Vec<(i32, i32)>,
     alive_cells: usize,
+    /// Bumped whenever the board changes, so the canvas knows when to rebuild
+    /// its texture.
+    board_revision: u64,
+    /// Board picture uploaded to the GPU, with the revision, region and colors it
+    /// was drawn from.
+    canvas: Option<(CanvasKey, egui::TextureHandle)>,
+    camera: Camera,
+    /// While set the camera fits the whole board each frame; panning or
+    /// zooming by hand clears it.
+    camera_follows_view: bool,
+    /// Canvas size on the last frame, for camera commands from the side panel.
+    canvas_size: egui::Vec2,
+    /// Set when a Space-drag panned, so releasing Space does not toggle pause.
+    space_panned: bool,
+    rule: Rule,
+    rule_input: String,
+    rule_error: Option<String>,
+    /// Soup seed as typed; a `u64` does not survive a round trip through the
+    /// `f64` of a drag value.
+    seed_input: String,
+    seed_error: Option<String>,
+    topology: Topology,
+    /// HashLife universe backing the grid while stepping by `2^k` generations;
+    /// rebuilt from the grid after any edit.
+    universe: Option<HashLife>,
+    /// Part of the universe copied onto the plane after a HashLife step, or
+    /// `None` when the plane holds every live cell.
+    plane_window: Option<Bounds>,
+    engine_error: Option<String>,
+    /// Result of the last pattern file or clipboard action.
+    pattern_message: Option<String>,
+    history: History<Snapshot>,
+    /// Set while a mouse drag is painting, so the whole stroke undoes at once.
+    stroke_active: bool,
+    /// Set once the current run has saved its undo entry, so everything from
+    /// resuming to pausing undoes at once.
+    run_recorded: bool,
+    /// Cell painted on the previous frame of a freehand stroke, joined to the
+    /// next one by a line.
+    last_painted: Option<(i64, i64)>,
+    /// Where a figure drag began and the state it paints.
+    figure_start: Option<((i64, i64), Cell)>,
+    tool: Tool,
+    /// Selected rectangle of cells, and the corner a selection drag began at.
+    selection: Option<Bounds>,
+    selection_anchor: Option<(i64, i64)>,
+    /// Last copied or cut cells.
+    clipboard: Option<Clip>,
+    /// Cells following the cursor as a ghost until they are placed.
+    pasting: Option<Clip>,
+    /// Generations since the last edit, for stepping backwards.
+    timeline: Timeline,
+    /// Generation at which a "Run to" request pauses the simulation.
+    run_target: Option<u64>,
+    /// Watches single-generation steps for still lifes, oscillators and
+    /// spaceships.
+    cycle: CycleDetector,
+    /// Result of the last "Analyze motion" request.
+    motion_message: Option<String>,
+    /// Objects found by the last census, with the generation it was taken at.
+    census: Option<(u64, Census)>,
+    /// Census still being taken on a worker thread, so a large unsettled
+    /// board does not freeze the window.
+    pending_census: Option<(u64, mpsc::Receiver<Census>)>,
 }
 
 struct Settings {
     grid_width: usize,
     grid_height: usize,
     updates_per_second: u32,
+    step_exponent: u8,
+    stepper: Stepper,
+    /// Generations run by "Advance".
+    advance_count: u64,
+    /// Generation entered for "Run to".
+    target_generation: u64,
+    pause_when_stable: bool,
+    /// Cells this far apart are counted as one object by the census.
+    census_distance: i64,
+    paste_mode: PasteMode,
+    /// Whether rectangles and ellipses are drawn solid.
+    fill_figures: bool,
+    soup: Soup,
+    /// Size of the centred region "Randomize" fills; the whole view if unset.
+    soup_size: Option<(i64, i64)>,
     alive_color: Color32,
     dead_color: Color32,
     background_color: Color32,
@@ -36,6 +192,16 @@ impl Settings {
             grid_width: width,
             grid_height: height,
             updates_per_second: 10,
+            step_exponent: 0,
+            stepper: Stepper::default(),
+            advance_count: 10,
+            target_generation: 100,
+            pause_when_stable: false,
+            census_distance: 2,
+            paste_mode: PasteMode::default(),
+            fill_figures: false,
+            soup: Soup::default(),
+            soup_size: None,
             alive_color: Color32::from_rgb(0x3b, 0xd9, 0x20),
             dead_color: Color32::from_rgb(0x24, 0x2b, 0x30),
             background_color: Color32::from_rgb(0x0f, 0x12, 0x14),
@@ -52,40 +218,175 @@ impl GameApp {
     pub fn new(_cc: &eframe::CreationContext<'_>) -> Self {
         let width = 80;
         let height = 60;
-        let settings = Settings::new(width, height);
+        let mut settings = Settings::new(width, height);
+        settings.soup.seed = rand::random();
+        let seed_input = settings.soup.seed.to_string();
         let mut grid = Grid::new(width, height);
-        grid.randomize();
+        settings
+            .soup
+            .fill(&mut grid, Bounds::new(0, 0, width as i64, height as i64));
 
         let mut app = Self {
             grid,
+            plane: SparseGrid::new(),
+            unbounded: false,
+            plane_view: Bounds::new(0, 0, width as i64, height as i64),
             generation: 0,
             paused: false,
             last_step: Instant::now(),
             settings,
             selected_pattern: 0,
+            pattern_cells: PATTERNS[0].cells.to_vec(),
             alive_cells: 0,
+            board_revision: 0,
+            canvas: None,
+            camera: Camera::default(),
+            camera_follows_view: true,
+            canvas_size: egui::Vec2::ZERO,
+            space_panned: false,
+            rule: Rule::conway(),
+            rule_input: Rule::conway().to_string(),
+            rule_error: None,
+            seed_input,
+            seed_error: None,
+            topology: Topology::default(),
+            universe: None,
+            plane_window: None,
+            engine_error: None,
+            pattern_message: None,
+            history: History::new(HISTORY_BUDGET_BYTES),
+            stroke_active: false,
+            run_recorded: false,
+            last_painted: None,
+            figure_start: None,
+            tool: Tool::Draw,
+            selection: None,
+            selection_anchor: None,
+            clipboard: None,
+            pasting: None,
+            timeline: Timeline::new(TIMELINE_BUDGET_BYTES),
+            run_target: None,
+            cycle: CycleDetector::new(),
+            motion_message: None,
+            census: None,
+            pending_census: None,
         };
-        app.recount_alive();
+        app.grid_edited();
         app
     }
 
     fn toggle_pause(&mut self) {
         self.paused = !self.paused;
+        if self.paused {
+            self.run_target = None;
+        }
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            grid: self.grid.clone(),
+            plane: self.plane.clone(),
+            unbounded: self.unbounded,
+            plane_view: self.plane_view,
+            generation: self.generation,
+        }
+    }
+
+    fn restore(&mut self, snapshot: Snapshot) {
+        self.grid = snapshot.grid;
+        self.plane = snapshot.plane;
+        self.unbounded = snapshot.unbounded;
+        self.plane_view = snapshot.plane_view;
+        self.generation = snapshot.generation;
+        if !self.unbounded {
+            self.settings.grid_width = self.grid.width();
+            self.settings.grid_height = self.grid.height();
+        }
+        self.grid_edited();
+    }
+
+    /// Saves the current board so the action about to run can be undone.
+    fn record_history(&mut self) {
+        self.sync_plane();
+        let snapshot = self.snapshot();
+        let cost = snapshot.cost();
+        self.history.record(snapshot, cost);
+    }
+
+    fn undo(&mut self) {
+        self.sync_plane();
+        let current = self.snapshot();
+        let cost = current.cost();
+        if let Some(snapshot) = self.history.undo(current, cost) {
+            self.paused = true;
+            self.run_target = None;
+            self.restore(snapshot);
+        }
+    }
+
+    fn redo(&mut self) {
+        self.sync_plane();
+        let current = self.snapshot();
+        let cost = current.cost();
+        if let Some(snapshot) = self.history.redo(current, cost) {
+            self.paused = true;
+            self.run_target = None;
+            self.restore(snapshot);
+        }
     }
 
+    /// Fills the board with a soup from a fresh seed.
     fn randomize(&mut self) {
-        self.grid.randomize();
+        self.settings.soup.seed = rand::random();
+        self.fill_soup();
+    }
+
+    /// Replaces the board with the soup described by the settings, so a
+    /// shown seed can be typed back in to recreate it.
+    fn fill_soup(&mut self) {
+        self.record_history();
+        self.seed_input = self.settings.soup.seed.to_string();
+        self.seed_error = None;
+        if self.unbounded {
+            self.plane_view = self.default_view();
+        }
+        let region = self.soup_region();
+        let soup = self.settings.soup;
+        let board = self.board_mut();
+        board.clear();
+        soup.fill(board, region);
         self.generation = 0;
-        self.recount_alive();
+        self.grid_edited();
+    }
+
+    /// The whole view, or a centred sub-rectangle of it when limited.
+    fn soup_region(&self) -> Bounds {
+        let view = self.view();
+        let Some((width, height)) = self.settings.soup_size else {
+            return view;
+        };
+        let width = width.min(view.width());
+        let height = height.min(view.height());
+        Bounds::new(
+            view.left + (view.width() - width) / 2,
+            view.top + (view.height() - height) / 2,
+            width,
+            height,
+        )
     }
 
     fn clear(&mut self) {
-        self.grid.clear();
+        self.record_history();
+        self.board_mut().clear();
+        if self.unbounded {
+            self.plane_view = self.default_view();
+        }
         self.generation = 0;
-        self.recount_alive();
+        self.grid_edited();
     }
 
     fn apply_resize(&mut self) {
+        self.record_history();
         let mut new_grid = Grid::new(self.settings.grid_width, self.settings.grid_height);
         let max_y = usize::min(new_grid.height(), self.grid.height());
         let max_x = usize::min(new_grid.width(), self.grid.width());
@@ -97,285 +398,1839 @@ impl GameApp {
         }
 
         self.grid = new_grid;
+        if self.unbounded {
+            self.refit_view();
+        }
         self.generation = 0;
-        self.recount_alive();
+        self.grid_edited();
     }
 
-    fn insert_selected_pattern(&mut self) {
-        let pattern = &PATTERNS[self.selected_pattern];
-        let center_x = (self.grid.width() / 2) as i32;
-        let center_y = (self.grid.height() / 2) as i32;
-
-        for &(dx, dy) in pattern.cells {
-            let x = center_x + dx;
-            let y = center_y + dy;
-            if x >= 0 && y >= 0 {
-                let ux = x as usize;
-                let uy = y as usize;
-                if ux < self.grid.width() && uy < self.grid.height() {
-                    self.grid.set(ux, uy, Cell::Alive);
-                }
+    fn set_unbounded(&mut self, unbounded: bool) {
+        self.record_history();
+        if unbounded {
+            self.plane = SparseGrid::from_board(&self.grid);
+            self.unbounded = true;
+            self.refit_view();
+        } else {
+            self.grid.clear();
+            for (x, y) in self.plane.live_cells() {
+                Board::set(&mut self.grid, x, y, Cell::Alive);
             }
+            let dropped = self.plane.population() - self.grid.population();
+            if dropped > 0 {
+                let cells = if dropped == 1 { "cell" } else { "cells" };
+                self.pattern_message = Some(format!(
+                    "Dropped {dropped} live {cells} outside the grid; undo to get them back"
+                ));
+            }
+            self.plane = SparseGrid::new();
+            self.unbounded = false;
         }
+        self.grid_edited();
+    }
 
-        self.generation = 0;
-        self.recount_alive();
+    fn board(&self) -> &dyn Board {
+        if self.unbounded {
+            &self.plane
+        } else {
+            &self.grid
+        }
     }
 
-    fn recount_alive(&mut self) {
-        let mut count = 0usize;
-        for y in 0..self.grid.height() {
-            for x in 0..self.grid.width() {
-                if self.grid.get(x, y).is_alive() {
-                    count += 1;
-                }
-            }
+    fn board_mut(&mut self) -> &mut dyn Board {
+        self.sync_plane();
+        if self.unbounded {
+            &mut self.plane
+        } else {
+            &mut self.grid
         }
-        self.alive_cells = count;
     }
 
-    fn handle_keyboard(&mut self, ctx: &egui::Context) {
-        ctx.input(|input| {
-            if input.key_pressed(egui::Key::Space) {
-                self.toggle_pause();
-            }
-            if input.key_pressed(egui::Key::R) {
-                self.randomize();
-            }
-            if input.key_pressed(egui::Key::C) {
-                self.clear();
+    /// Cells currently shown on the canvas.
+    fn view(&self) -> Bounds {
+        if self.unbounded {
+            self.plane_view
+        } else {
+            Bounds::new(0, 0, self.grid.width() as i64, self.grid.height() as i64)
+        }
+    }
+
+    fn default_view(&self) -> Bounds {
+        Bounds::new(
+            0,
+            0,
+            self.settings.grid_width as i64,
+            self.settings.grid_height as i64,
+        )
+    }
+
+    /// Re-centres the unbounded view on the pattern, keeping at least the
+    /// size chosen by the grid sliders.
+    fn refit_view(&mut self) {
+        self.plane_view = match self.pattern_bounds() {
+            Some(bounds) => bounds.grown_to(
+                self.settings.grid_width as i64,
+                self.settings.grid_height as i64,
+                VIEW_MARGIN,
+            ),
+            None => self.default_view(),
+        };
+    }
+
+    fn follow_pattern(&mut self) {
+        if let Some(bounds) = self.pattern_bounds() {
+            if !self.plane_view.contains_bounds(&bounds) {
+                self.refit_view();
             }
-        });
+        }
     }
 
-    fn maybe_step_simulation(&mut self) {
-        if self.paused {
-            return;
+    /// The library pattern as it will be stamped, centred on the cursor.
+    fn stamp_clip(&self) -> Clip {
+        Clip::from_offsets(&self.pattern_cells)
+    }
+
+    /// Stamps the library pattern centred on cell `(x, y)`; the tool stays
+    /// armed for the next click.
+    fn place_stamp(&mut self, x: i64, y: i64) {
+        self.record_history();
+        let clip = self.stamp_clip();
+        let mode = self.settings.paste_mode;
+        clip.paste(
+            self.board_mut(),
+            x - clip.width / 2,
+            y - clip.height / 2,
+            mode,
+        );
+        if self.unbounded {
+            self.follow_pattern();
         }
+        self.grid_edited();
+    }
 
-        if self.last_step.elapsed() >= self.settings.step_duration() {
-            self.grid = next_generation(&self.grid);
-            self.generation += 1;
-            self.last_step = Instant::now();
-            self.recount_alive();
+    /// Sets the given offsets alive around the centre of the view and returns
+    /// how many fell outside a bounded grid.
+    fn stamp_at_center(&mut self, cells: impl IntoIterator<Item = (i32, i32)>) -> usize {
+        let view = self.view();
+        let center_x = view.left + view.width() / 2;
+        let center_y = view.top + view.height() / 2;
+        let unbounded = self.unbounded;
+
+        let board = self.board_mut();
+        let mut clipped = 0;
+        for (dx, dy) in cells {
+            let x = center_x + dx as i64;
+            let y = center_y + dy as i64;
+            if !unbounded && !view.contains(x, y) {
+                clipped += 1;
+                continue;
+            }
+            board.set(x, y, Cell::Alive);
         }
+        if unbounded {
+            self.follow_pattern();
+        }
+        clipped
     }
 
-    fn render_controls(&mut self, ui: &mut egui::Ui) {
-        ui.heading("Simulation");
-        if ui
-            .button(if self.paused {
-                "Resume (Space)"
-            } else {
-                "Pause (Space)"
-            })
-            .clicked()
-        {
-            self.toggle_pause();
+    /// Places a pattern read from a file or the clipboard at the centre of the
+    /// view, adopting its rule if it declares one.
+    fn place_pattern_file(&mut self, pattern: PatternFile, replace: bool) {
+        self.record_history();
+        if replace {
+            self.board_mut().clear();
+            self.generation = 0;
+        }
+        if let Some(rule) = pattern.rule {
+            self.set_rule(rule);
         }
 
-        ui.horizontal(|ui| {
-            if ui.button("Randomize (R)").clicked() {
-                self.randomize();
-            }
-            if ui.button("Clear (C)").clicked() {
-                self.clear();
-            }
+        let (width, height) = pattern.size();
+        let centered = pattern
+            .cells
+            .iter()
+            .map(|&(x, y)| (x - width / 2, y - height / 2));
+        let clipped = self.stamp_at_center(centered);
+        self.grid_edited();
+
+        let name = pattern.name.as_deref().unwrap_or("pattern");
+        self.pattern_message = Some(if clipped > 0 {
+            format!("Loaded {name}; {clipped} cells did not fit in the grid")
+        } else {
+            format!("Loaded {name} ({} cells)", pattern.cells.len())
         });
+    }
 
-        ui.separator();
-        ui.heading("Speed");
-        if ui
-            .add(
-                Slider::new(&mut self.settings.updates_per_second, MIN_UPS..=MAX_UPS)
-                    .text("Updates / second"),
-            )
-            .changed()
-            && self.settings.updates_per_second < MIN_UPS
-        {
-            self.settings.updates_per_second = MIN_UPS;
+    /// Save dialog listing the formats the chosen extension can select.
+    fn pattern_dialog() -> rfd::FileDialog {
+        let mut dialog = rfd::FileDialog::new();
+        for format in PATTERN_FORMATS.iter().filter(|f| f.is_saved_by_extension()) {
+            dialog = dialog.add_filter(format.name(), format.extensions());
         }
+        dialog
+    }
 
-        ui.separator();
-        ui.heading("Grid Size");
-        let mut resized = false;
-        let width_changed = ui
-            .add(
-                Slider::new(&mut self.settings.grid_width, MIN_GRID_SIZE..=MAX_GRID_SIZE)
-                    .text("Width"),
-            )
-            .changed();
-        let height_changed = ui
-            .add(
-                Slider::new(
-                    &mut self.settings.grid_height,
-                    MIN_GRID_SIZE..=MAX_GRID_SIZE,
-                )
-                .text("Height"),
-            )
-            .changed();
-        if width_changed || height_changed {
-            resized = true;
+    fn open_pattern(&mut self) {
+        let all_extensions: Vec<&str> = PATTERN_FORMATS
+            .iter()
+            .flat_map(|format| format.extensions().iter().copied())
+            .collect();
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("All patterns", &all_extensions)
+            .pick_file()
+        else {
+            return;
+        };
+
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(err) => {
+                self.pattern_message = Some(format!("Could not open {}: {err}", path.display()));
+                return;
+            }
+        };
+        if PatternFormat::detect(&text) == PatternFormat::Macrocell {
+            self.load_macrocell(&text);
+            return;
         }
-        if resized {
-            self.apply_resize();
+
+        match parse_any(&text) {
+            Ok(pattern) => self.place_pattern_file(pattern, true),
+            Err(err) => {
+                self.pattern_message = Some(format!("Could not open {}: {err}", path.display()))
+            }
         }
+    }
 
-        ui.separator();
-        ui.heading("Colors");
-        ui.horizontal(|ui| {
-            ui.label("Alive");
-            color_picker::color_edit_button_srgba(
-                ui,
-                &mut self.settings.alive_color,
-                Alpha::Opaque,
-            );
-        });
-        ui.horizontal(|ui| {
-            ui.label("Dead");
-            color_picker::color_edit_button_srgba(ui, &mut self.settings.dead_color, Alpha::Opaque);
-        });
-        ui.horizontal(|ui| {
-            ui.label("Background");
-            color_picker::color_edit_button_srgba(
-                ui,
-                &mut self.settings.background_color,
-                Alpha::Opaque,
-            );
-        });
+    /// Macrocell patterns are never clipped to the bounded grid: they either
+    /// fit whole or the user is told to switch to the unbounded plane. On the
+    /// plane the parsed universe is kept for HashLife stepping. Nothing
+    /// changes, and no history is recorded, when loading fails.
+    fn load_macrocell(&mut self, text: &str) {
+        let loaded = match macrocell::parse(text) {
+            Ok(loaded) => loaded,
+            Err(err) => {
+                self.pattern_message = Some(format!("Could not open pattern: {err}"));
+                return;
+            }
+        };
+        let name = loaded.info.name.as_deref().unwrap_or("pattern");
+        let population = loaded.universe.population();
 
-        ui.separator();
-        ui.heading("Patterns");
-        ComboBox::from_label("Pattern")
-            .selected_text(PATTERNS[self.selected_pattern].name)
-            .show_ui(ui, |ui| {
-                for (idx, pattern) in PATTERNS.iter().enumerate() {
-                    ui.selectable_value(&mut self.selected_pattern, idx, pattern.name);
-                }
-            });
-        if ui.button("Insert Pattern").clicked() {
-            self.insert_selected_pattern();
+        if self.unbounded {
+            if population > MAX_CELL_LIST_POPULATION as u64 {
+                self.pattern_message = Some(format!(
+                    "Could not open {name}: it has {population} live cells and the unbounded \
+                     plane holds at most {MAX_CELL_LIST_POPULATION}"
+                ));
+                return;
+            }
+            self.record_history();
+            self.plane.clear();
+            loaded.universe.write_to_board(&mut self.plane);
+            self.refit_view();
+        } else {
+            let mut grid = Grid::new(self.grid.width(), self.grid.height());
+            if let Err(err) = macrocell::load_into_grid(&loaded.universe, &mut grid) {
+                self.pattern_message = Some(format!("Could not open {name}: {err}"));
+                return;
+            }
+            self.record_history();
+            self.grid = grid;
+        }
+
+        self.set_rule(loaded.universe.rule());
+        self.generation = 0;
+        self.grid_edited();
+        if self.unbounded {
+            self.universe = Some(loaded.universe);
         }
+        self.pattern_message = Some(format!("Loaded {name} ({population} cells)"));
     }
 
-    fn render_status(&self, ui: &mut egui::Ui) {
-        ui.horizontal(|ui| {
-            ui.label(RichText::new(format!("Generation: {}", self.generation)).strong());
-            ui.separator();
-            ui.label(format!(
-                "Alive cells: {} / {}",
-                self.alive_cells,
-                self.grid.width() * self.grid.height()
-            ));
-            ui.separator();
-            ui.label(format!(
-                "Grid: {} x {} | Speed: {} UPS",
-                self.grid.width(),
-                self.grid.height(),
-                self.settings.updates_per_second
-            ));
+    fn save_pattern(&mut self) {
+        self.sync_plane();
+        let pattern = match PatternFile::from_board(self.board(), self.rule) {
+            Ok(pattern) => pattern,
+            Err(err) => {
+                self.pattern_message = Some(format!("Could not save: {err}"));
+                return;
+            }
+        };
+        let Some(path) = Self::pattern_dialog()
+            .set_file_name("pattern.rle")
+            .save_file()
+        else {
+            return;
+        };
+
+        let format = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map_or(PatternFormat::Rle, PatternFormat::from_extension);
+        let result = format
+            .write(&pattern)
+            .map_err(|err| err.to_string())
+            .and_then(|text| fs::write(&path, text).map_err(|err| err.to_string()));
+        self.pattern_message = Some(match result {
+            Ok(()) => format!("Saved {} as {}", path.display(), format.name()),
+            Err(err) => format!("Could not save {}: {err}", path.display()),
         });
     }
 
-    fn render_canvas(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
-        let available_size = ui.available_size();
-        let grid_width = self.grid.width() as f32;
-        let grid_height = self.grid.height() as f32;
-        if grid_width <= 0.0 || grid_height <= 0.0 {
-            return;
-        }
-        let mut cell_size = (available_size.x / grid_width)
-            .min(available_size.y / grid_height)
-            .max(4.0);
-        if !cell_size.is_finite() {
-            cell_size = 4.0;
+    fn copy_rle(&mut self, ctx: &egui::Context) {
+        self.sync_plane();
+        match PatternFile::from_board(self.board(), self.rule) {
+            Ok(pattern) => {
+                ctx.copy_text(rle::write(&pattern));
+                self.pattern_message = Some("Copied board as RLE".to_string());
+            }
+            Err(err) => self.pattern_message = Some(format!("Could not copy: {err}")),
         }
-        let grid_size = egui::vec2(cell_size * grid_width, cell_size * grid_height);
-        let (response, painter) =
-            ui.allocate_painter(grid_size, Sense::click_and_drag().union(Sense::hover()));
-        let rect = response.rect;
+    }
 
-        painter.rect_filled(rect, 0.0, self.settings.background_color);
+    /// Copies the selection to the internal and system clipboards, or the
+    /// whole board as RLE when nothing is selected.
+    fn copy_selection(&mut self, ctx: &egui::Context) {
+        let Some(region) = self.selection else {
+            self.copy_rle(ctx);
+            return;
+        };
+        self.sync_plane();
+        let clip = Clip::copy(self.board(), region);
+        self.pattern_message = Some(format!("Copied {} cells", clip.cells.len()));
+        self.put_on_clipboard(clip, ctx);
+    }
 
-        for y in 0..self.grid.height() {
-            for x in 0..self.grid.width() {
-                let cell = self.grid.get(x, y);
-                let min = egui::pos2(
-                    rect.left() + x as f32 * cell_size,
-                    rect.top() + y as f32 * cell_size,
-                );
-                let max = egui::pos2(min.x + cell_size, min.y + cell_size);
-                let cell_rect = egui::Rect::from_min_max(min, max);
-                let color = if cell.is_alive() {
-                    self.settings.alive_color
-                } else {
-                    self.settings.dead_color
-                };
-                painter.rect_filled(cell_rect, 0.0, color);
-                painter.rect_stroke(
-                    cell_rect,
-                    0.0,
-                    egui::Stroke::new(0.5, self.settings.grid_line_color),
-                );
-            }
+    fn cut_selection(&mut self, ctx: &egui::Context) {
+        let Some(region) = self.selection else {
+            return;
+        };
+        self.record_history();
+        let clip = Clip::cut(self.board_mut(), region);
+        self.grid_edited();
+        self.pattern_message = Some(format!("Cut {} cells", clip.cells.len()));
+        self.put_on_clipboard(clip, ctx);
+    }
+
+    fn put_on_clipboard(&mut self, clip: Clip, ctx: &egui::Context) {
+        match clip.to_pattern(self.rule) {
+            Ok(pattern) => ctx.copy_text(rle::write(&pattern)),
+            Err(err) => self.pattern_message = Some(format!("Could not copy as RLE: {err}")),
         }
+        self.clipboard = Some(clip);
+    }
 
-        self.handle_pointer_input(&response, rect, cell_size, ctx);
+    /// Starts a paste preview of `text` from the system clipboard, falling
+    /// back to the internal clipboard when it is not a pattern.
+    fn start_paste(&mut self, text: Option<&str>) {
+        let parsed = text.map(parse_any);
+        self.pasting = match &parsed {
+            Some(Ok(pattern)) => Some(Clip::from_pattern(pattern)),
+            _ => self.clipboard.clone(),
+        };
+        if self.pasting.is_none() {
+            self.pattern_message = Some(match parsed {
+                Some(Err(err)) => format!("Clipboard is not a pattern: {err}"),
+                _ => "Nothing to paste".to_string(),
+            });
+        }
     }
 
-    fn handle_pointer_input(
-        &mut self,
-        response: &egui::Response,
-        rect: egui::Rect,
-        cell_size: f32,
-        ctx: &egui::Context,
-    ) {
-        let (primary_down, secondary_down) =
-            ctx.input(|input| (input.pointer.primary_down(), input.pointer.secondary_down()));
-        if !primary_down && !secondary_down {
+    /// Places the paste preview with its top-left corner at `(x, y)`.
+    fn place_paste(&mut self, x: i64, y: i64) {
+        let Some(clip) = self.pasting.take() else {
             return;
+        };
+        self.record_history();
+        let mode = self.settings.paste_mode;
+        clip.paste(self.board_mut(), x, y, mode);
+        if self.unbounded {
+            self.follow_pattern();
         }
+        self.grid_edited();
+    }
 
-        if let Some(pointer_pos) = response.interact_pointer_pos() {
-            if !rect.contains(pointer_pos) {
+    /// Rotates or flips the paste preview, else the selection in place, else
+    /// the library pattern waiting to be stamped.
+    fn transform(&mut self, transform: Transform) {
+        if let Some(clip) = &self.pasting {
+            self.pasting = Some(clip.transformed(transform));
+        } else if let (Some(region), false) = (self.selection, self.tool == Tool::Stamp) {
+            let snapshot = self.snapshot();
+            let Some(turned) = Clip::transform_region(self.board_mut(), region, transform) else {
+                self.pattern_message =
+                    Some("Cannot turn the selection: it would leave the grid".to_string());
                 return;
-            }
+            };
+            let cost = snapshot.cost();
+            self.history.record(snapshot, cost);
+            self.selection = Some(turned);
+            self.grid_edited();
+        } else {
+            transform.apply_to_offsets(&mut self.pattern_cells);
+        }
+    }
 
-            let x = ((pointer_pos.x - rect.left()) / cell_size).floor() as usize;
-            let y = ((pointer_pos.y - rect.top()) / cell_size).floor() as usize;
+    fn center_on_selection(&mut self) {
+        if let Some(selection) = self.selection {
+            self.camera.center_on(selection, self.canvas_size);
+            self.camera_follows_view = false;
+        }
+    }
 
-            if x < self.grid.width() && y < self.grid.height() {
-                if primary_down {
-                    self.grid.set(x, y, Cell::Alive);
-                } else if secondary_down {
-                    self.grid.set(x, y, Cell::Dead);
-                }
-                self.recount_alive();
+    fn set_rule(&mut self, rule: Rule) {
+        self.rule = rule;
+        self.rule_input = rule.to_string();
+        self.rule_error = None;
+        self.drop_universe();
+        self.restart_cycle_detection();
+    }
+
+    fn apply_rule_input(&mut self) {
+        match Rule::parse(&self.rule_input) {
+            Ok(rule) => self.set_rule(rule),
+            Err(err) => self.rule_error = Some(err.to_string()),
+        }
+    }
+
+    fn apply_seed_input(&mut self) {
+        match self.seed_input.trim().parse() {
+            Ok(seed) => {
+                self.settings.soup.seed = seed;
+                self.fill_soup();
             }
+            Err(err) => self.seed_error = Some(format!("Invalid seed: {err}")),
         }
     }
-}
 
-impl eframe::App for GameApp {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        self.handle_keyboard(ctx);
-        self.maybe_step_simulation();
+    /// Must be called after any direct change to `self.grid` or `self.plane`.
+    fn grid_edited(&mut self) {
+        self.drop_universe();
+        self.board_changed();
+        let frame = self.current_frame();
+        self.timeline.reset(frame);
+        self.restart_cycle_detection();
+    }
 
-        egui::SidePanel::left("control_panel")
-            .resizable(false)
-            .default_width(260.0)
-            .show(ctx, |ui| {
-                self.render_controls(ui);
-            });
+    /// Treats the current board as the first generation to compare against,
+    /// after edits or anything else that breaks the sequence of generations.
+    fn restart_cycle_detection(&mut self) {
+        self.cycle.reset();
+        self.observe_cycle();
+    }
 
-        egui::CentralPanel::default().show(ctx, |ui| {
-            self.render_canvas(ui, ctx);
-        });
+    fn observe_cycle(&mut self) {
+        let board: &dyn Board = if self.unbounded {
+            &self.plane
+        } else {
+            &self.grid
+        };
+        self.cycle.observe(board, self.generation);
+    }
 
-        egui::TopBottomPanel::bottom("status_panel").show(ctx, |ui| {
-            self.render_status(ui);
-        });
+    fn current_frame(&self) -> Frame {
+        Frame {
+            generation: self.generation,
+            cells: self.board().live_cells().collect(),
+        }
+    }
 
-        ctx.request_repaint_after(Duration::from_millis(16));
+    /// Puts a retained generation back on the board without disturbing the
+    /// rest of the timeline.
+    fn show_frame(&mut self, index: usize) {
+        let Some(frame) = self.timeline.seek(index).cloned() else {
+            return;
+        };
+        self.paused = true;
+        self.run_target = None;
+        self.drop_universe();
+        self.generation = frame.generation;
+        let board = self.board_mut();
+        board.clear();
+        for (x, y) in frame.cells {
+            board.set(x, y, Cell::Alive);
+        }
+        if self.unbounded {
+            self.follow_pattern();
+        }
+        self.board_changed();
+        self.restart_cycle_detection();
+    }
+
+    fn step_back(&mut self) {
+        if let Some(index) = self.timeline.position().checked_sub(1) {
+            self.show_frame(index);
+        }
+    }
+
+    /// Must be called after the cells on the board change in any way.
+    fn board_changed(&mut self) {
+        self.alive_cells = self.board().population();
+        self.board_revision += 1;
+    }
+
+    fn handle_keyboard(&mut self, ctx: &egui::Context) {
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+
+        let (pasted, copied, cut) = ctx.input(|input| {
+            // Space toggles on release, unless it was held to pan the canvas.
+            if input.key_released(egui::Key::Space) {
+                if !self.space_panned {
+                    self.toggle_pause();
+                }
+                self.space_panned = false;
+            }
+            if input.modifiers.command {
+                if input.key_pressed(egui::Key::Z) {
+                    if input.modifiers.shift {
+                        self.redo();
+                    } else {
+                        self.undo();
+                    }
+                }
+                if input.key_pressed(egui::Key::Y) {
+                    self.redo();
+                }
+            } else {
+                if input.key_pressed(egui::Key::Comma) {
+                    self.step_back();
+                }
+                if input.key_pressed(egui::Key::N) || input.key_pressed(egui::Key::Period) {
+                    self.advance(1);
+                }
+                if input.key_pressed(egui::Key::R) {
+                    self.randomize();
+                }
+                if input.key_pressed(egui::Key::C) {
+                    self.clear();
+                }
+                if input.key_pressed(egui::Key::F) {
+                    self.fit_to_pattern();
+                }
+                if input.key_pressed(egui::Key::S) {
+                    self.tool = Tool::Select;
+                }
+                if input.key_pressed(egui::Key::D) {
+                    self.tool = Tool::Draw;
+                }
+                if input.key_pressed(egui::Key::P) {
+                    self.tool = Tool::Stamp;
+                }
+                if input.key_pressed(egui::Key::L) {
+                    self.tool = Tool::Figure(Figure::Line);
+                }
+                if input.key_pressed(egui::Key::B) {
+                    self.tool = Tool::Figure(Figure::Rectangle);
+                }
+                if input.key_pressed(egui::Key::E) {
+                    self.tool = Tool::Figure(Figure::Ellipse);
+                }
+                if input.key_pressed(egui::Key::G) {
+                    self.tool = Tool::Fill;
+                }
+                if input.key_pressed(egui::Key::M) {
+                    self.cycle_paste_mode();
+                }
+                if input.key_pressed(egui::Key::OpenBracket) {
+                    self.transform(Transform::RotateAnticlockwise);
+                }
+                if input.key_pressed(egui::Key::CloseBracket) {
+                    self.transform(Transform::RotateClockwise);
+                }
+                if input.key_pressed(egui::Key::H) {
+                    self.transform(Transform::FlipHorizontal);
+                }
+                if input.key_pressed(egui::Key::V) {
+                    self.transform(Transform::FlipVertical);
+                }
+            }
+            if input.key_pressed(egui::Key::Escape) {
+                if self.pasting.is_some() {
+                    self.pasting = None;
+                } else if self.figure_start.is_some() {
+                    self.figure_start = None;
+                } else if self.tool == Tool::Stamp {
+                    self.tool = Tool::Draw;
+                } else {
+                    self.selection = None;
+                }
+            }
+            let pasted = input.events.iter().find_map(|event| match event {
+                egui::Event::Paste(text) => Some(text.clone()),
+                _ => None,
+            });
+            let copied = input.events.contains(&egui::Event::Copy);
+            let cut = input.events.contains(&egui::Event::Cut);
+            (pasted, copied, cut)
+        });
+        if let Some(text) = pasted {
+            self.start_paste(Some(&text));
+        }
+        if copied {
+            self.copy_selection(ctx);
+        }
+        if cut {
+            self.cut_selection(ctx);
+        }
+    }
+
+    /// Switches to the next of `PASTE_MODES`, wrapping around.
+    fn cycle_paste_mode(&mut self) {
+        let index = PASTE_MODES
+            .iter()
+            .position(|&mode| mode == self.settings.paste_mode)
+            .unwrap_or(0);
+        self.settings.paste_mode = PASTE_MODES[(index + 1) % PASTE_MODES.len()];
+        self.pattern_message = Some(format!("Paste mode: {}", self.settings.paste_mode.name()));
+    }
+
+    fn maybe_step_simulation(&mut self) {
+        if self.paused {
+            self.run_recorded = false;
+            return;
+        }
+
+        if self.last_step.elapsed() >= self.settings.step_duration() {
+            if !self.run_recorded {
+                self.record_history();
+                self.run_recorded = true;
+            }
+            let was_stable = self.cycle.result().is_some();
+            self.step_once(self.update_exponent());
+            self.last_step = Instant::now();
+
+            if self.settings.pause_when_stable && !was_stable && self.cycle.result().is_some() {
+                self.paused = true;
+                self.run_target = None;
+            }
+
+            if self
+                .run_target
+                .is_some_and(|target| self.generation >= target)
+            {
+                self.paused = true;
+                self.run_target = None;
+            }
+        }
+    }
+
+    /// The step exponent for the next timed update, lowered so a "Run to"
+    /// target is not overshot.
+    fn update_exponent(&self) -> u8 {
+        let mut exponent = self.settings.step_exponent;
+        if let Some(target) = self.run_target {
+            let remaining = target.saturating_sub(self.generation);
+            while exponent > 0 && 1u64 << exponent > remaining {
+                exponent -= 1;
+            }
+        }
+        exponent
+    }
+
+    /// Advances one generation, or `2^exponent` generations through HashLife.
+    fn step_once(&mut self, exponent: u8) {
+        if exponent == 0 {
+            self.drop_universe();
+            if self.unbounded {
+                self.plane = next_generation_unbounded(&self.plane, &self.rule);
+            } else {
+                self.grid = self
+                    .settings
+                    .stepper
+                    .step(&self.grid, &self.rule, self.topology);
+            }
+            self.generation += 1;
+        } else {
+            self.step_hashlife(exponent);
+        }
+        if self.unbounded {
+            self.follow_pattern();
+        }
+        self.board_changed();
+        if exponent == 0 {
+            self.observe_cycle();
+        } else {
+            self.restart_cycle_detection();
+        }
+        let frame = self.current_frame();
+        self.timeline.push(frame);
+    }
+
+    /// Runs `count` single generations immediately, as one undoable action.
+    fn advance(&mut self, count: u64) {
+        self.paused = true;
+        self.run_target = None;
+        self.record_history();
+        for _ in 0..count {
+            self.step_once(0);
+        }
+    }
+
+    fn analyze_motion(&mut self) {
+        self.sync_plane();
+        if self.alive_cells == 0 {
+            self.motion_message = Some("The board is empty".to_string());
+            return;
+        }
+        let velocity = find_velocity(self.board().live_cells(), &self.rule, MAX_ANALYZED_PERIOD);
+        self.motion_message = Some(match velocity {
+            Some(velocity) if velocity.is_moving() => format!(
+                "{velocity} spaceship (period {}, moves {}, {})",
+                velocity.period, velocity.dx, velocity.dy
+            ),
+            Some(velocity) if velocity.period == 1 => "Still life".to_string(),
+            Some(velocity) => format!("Oscillator with period {}", velocity.period),
+            None => format!("No repeat within {MAX_ANALYZED_PERIOD} generations"),
+        });
+    }
+
+    fn run_to(&mut self, target: u64) {
+        if target > self.generation {
+            self.run_target = Some(target);
+            self.paused = false;
+        }
+    }
+
+    fn step_hashlife(&mut self, exponent: u8) {
+        if self.universe.is_none() {
+            match HashLife::from_board(self.board(), self.rule) {
+                Ok(universe) => self.universe = Some(universe),
+                Err(err) => {
+                    self.engine_error = Some(err.to_string());
+                    self.settings.step_exponent = 0;
+                    return;
+                }
+            }
+        }
+
+        if let Some(universe) = &mut self.universe {
+            if let Err(err) = universe.step(exponent) {
+                self.engine_error = Some(err.to_string());
+                self.settings.step_exponent = 0;
+                self.paused = true;
+                self.run_target = None;
+                return;
+            }
+            if self.unbounded {
+                // Copying every cell of a fast-growing pattern each frame would
+                // not finish; the rest is written back by `sync_plane`.
+                let window = self.camera.visible(self.canvas_size);
+                universe.write_window_to_board(&mut self.plane, window);
+                self.plane_window = Some(window);
+            } else {
+                universe.write_to_board(&mut self.grid);
+            }
+            self.generation = self.generation.wrapping_add(1u64 << exponent);
+        }
+    }
+
+    /// Leaves HashLife stepping, keeping every cell of the universe.
+    fn drop_universe(&mut self) {
+        self.sync_plane();
+        self.universe = None;
+    }
+
+    /// Copies the whole universe onto the plane when only a window of it was
+    /// written, before anything edits or reads the plane as a whole.
+    fn sync_plane(&mut self) {
+        if self.plane_window.take().is_some() {
+            if let Some(universe) = &self.universe {
+                universe.write_to_board(&mut self.plane);
+            }
+            self.board_changed();
+        }
+    }
+
+    /// Rewrites the plane's window of the universe once panning or zooming
+    /// uncovers cells outside it.
+    fn refresh_plane_window(&mut self) {
+        let visible = self.camera.visible(self.canvas_size);
+        if let (Some(window), Some(universe)) = (self.plane_window, &self.universe) {
+            if !window.contains_bounds(&visible) {
+                universe.write_window_to_board(&mut self.plane, visible);
+                self.plane_window = Some(visible);
+                self.board_changed();
+            }
+        }
+    }
+
+    /// Bounding box of every live cell, including those of a HashLife
+    /// universe not yet copied onto the plane.
+    fn pattern_bounds(&self) -> Option<Bounds> {
+        match (self.plane_window, &self.universe) {
+            (Some(_), Some(universe)) => universe.bounding_box(),
+            _ => self.board().bounding_box(),
+        }
+    }
+
+    fn render_controls(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Simulation");
+        if ui
+            .button(if self.paused {
+                "Resume (Space)"
+            } else {
+                "Pause (Space)"
+            })
+            .clicked()
+        {
+            self.toggle_pause();
+        }
+
+        ui.horizontal(|ui| {
+            if ui.button("Randomize (R)").clicked() {
+                self.randomize();
+            }
+            if ui.button("Clear (C)").clicked() {
+                self.clear();
+            }
+        });
+        ui.horizontal(|ui| {
+            if ui.button("Step 1 (N)").clicked() {
+                self.advance(1);
+            }
+            if ui.button("Advance").clicked() {
+                self.advance(self.settings.advance_count);
+            }
+            ui.add(
+                egui::DragValue::new(&mut self.settings.advance_count)
+                    .range(1..=MAX_ADVANCE_COUNT)
+                    .suffix(" gens"),
+            );
+        });
+        ui.horizontal(|ui| {
+            if ui.button("Run to generation").clicked() {
+                self.run_to(self.settings.target_generation);
+            }
+            ui.add(egui::DragValue::new(&mut self.settings.target_generation));
+        });
+        if let Some(target) = self.run_target {
+            ui.label(format!("Running until generation {target}"));
+        }
+        ui.checkbox(&mut self.settings.pause_when_stable, "Pause when stable")
+            .on_hover_text(
+                "Pause once the board stops changing or starts repeating. Only single-generation \
+                 steps are checked, so this needs a step exponent of 0.",
+            );
+        if ui
+            .button("Analyze motion")
+            .on_hover_text(
+                "Run a copy of the board on the unbounded plane until its shape repeats \
+                 and report the period and speed.",
+            )
+            .clicked()
+        {
+            self.analyze_motion();
+        }
+        if let Some(message) = &self.motion_message {
+            ui.label(message);
+        }
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(self.history.can_undo(), egui::Button::new("Undo (Ctrl+Z)"))
+                .clicked()
+            {
+                self.undo();
+            }
+            if ui
+                .add_enabled(self.history.can_redo(), egui::Button::new("Redo (Ctrl+Y)"))
+                .clicked()
+            {
+                self.redo();
+            }
+        });
+
+        ui.separator();
+        ui.heading("Speed");
+        if ui
+            .add(
+                Slider::new(&mut self.settings.updates_per_second, MIN_UPS..=MAX_UPS)
+                    .text("Updates / second"),
+            )
+            .changed()
+            && self.settings.updates_per_second < MIN_UPS
+        {
+            self.settings.updates_per_second = MIN_UPS;
+        }
+        if ui
+            .add(
+                Slider::new(&mut self.settings.step_exponent, 0..=MAX_STEP_EXPONENT)
+                    .text("Step exponent (2^k)"),
+            )
+            .on_hover_text(
+                "Generations per update. Above 0 the HashLife engine runs on an unbounded \
+                 plane, so the edge topology is ignored and cells leaving the grid keep evolving.",
+            )
+            .changed()
+        {
+            self.engine_error = None;
+        }
+        if let Some(error) = &self.engine_error {
+            ui.colored_label(Color32::LIGHT_RED, error);
+        }
+
+        ui.separator();
+        ui.heading("Rule");
+        let preset_name = RULE_PRESETS
+            .iter()
+            .find(|preset| Rule::parse(preset.rulestring).ok() == Some(self.rule))
+            .map_or("Custom", |preset| preset.name);
+        let mut selected_rule = None;
+        ComboBox::from_label("Preset")
+            .selected_text(preset_name)
+            .show_ui(ui, |ui| {
+                for preset in RULE_PRESETS.iter() {
+                    let label = format!("{} ({})", preset.name, preset.rulestring);
+                    if ui
+                        .selectable_label(preset.name == preset_name, label)
+                        .clicked()
+                    {
+                        selected_rule = Rule::parse(preset.rulestring).ok();
+                    }
+                }
+            });
+        if let Some(rule) = selected_rule {
+            self.set_rule(rule);
+        }
+        ui.horizontal(|ui| {
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut self.rule_input)
+                    .desired_width(120.0)
+                    .hint_text("B3/S23"),
+            );
+            let submitted =
+                response.lost_focus() && ui.input(|input| input.key_pressed(egui::Key::Enter));
+            if ui.button("Apply").clicked() || submitted {
+                self.apply_rule_input();
+            }
+        });
+        if let Some(error) = &self.rule_error {
+            ui.colored_label(Color32::LIGHT_RED, error);
+        }
+
+        ui.separator();
+        ui.heading("Grid Size");
+        let mut resized = false;
+        let width_changed = ui
+            .add(
+                Slider::new(&mut self.settings.grid_width, MIN_GRID_SIZE..=MAX_GRID_SIZE)
+                    .logarithmic(true)
+                    .text("Width"),
+            )
+            .changed();
+        let height_changed = ui
+            .add(
+                Slider::new(
+                    &mut self.settings.grid_height,
+                    MIN_GRID_SIZE..=MAX_GRID_SIZE,
+                )
+                .logarithmic(true)
+                .text("Height"),
+            )
+            .changed();
+        if width_changed || height_changed {
+            resized = true;
+        }
+        if resized {
+            self.apply_resize();
+        }
+        let mut unbounded = self.unbounded;
+        if ui
+            .checkbox(&mut unbounded, "Unbounded plane")
+            .on_hover_text("Let patterns grow past the grid; the sliders set the minimum view.")
+            .changed()
+        {
+            self.set_unbounded(unbounded);
+        }
+        ui.add_enabled_ui(!self.unbounded, |ui| {
+            ComboBox::from_label("Edges")
+                .selected_text(self.topology.name())
+                .show_ui(ui, |ui| {
+                    for topology in TOPOLOGIES {
+                        if ui
+                            .selectable_value(&mut self.topology, topology, topology.name())
+                            .changed()
+                        {
+                            self.restart_cycle_detection();
+                        }
+                    }
+                });
+            ComboBox::from_label("Stepping")
+                .selected_text(self.settings.stepper.name())
+                .show_ui(ui, |ui| {
+                    for stepper in STEPPERS {
+                        ui.selectable_value(&mut self.settings.stepper, stepper, stepper.name())
+                            .on_hover_text("Parallel spreads row bands over every core.");
+                    }
+                });
+        });
+
+        ui.separator();
+        ui.heading("Camera");
+        ui.horizontal(|ui| {
+            if ui
+                .selectable_label(self.camera_follows_view, "Fit board")
+                .on_hover_text("Keep the whole board in view.")
+                .clicked()
+            {
+                self.camera_follows_view = true;
+            }
+            if ui.button("Fit to pattern (F)").clicked() {
+                self.fit_to_pattern();
+            }
+            if ui.button("Center on pattern").clicked() {
+                self.center_on_pattern();
+            }
+        });
+
+        ui.separator();
+        self.render_soup(ui);
+
+        ui.separator();
+        self.render_census(ui);
+
+        ui.separator();
+        ui.heading("Colors");
+        ui.horizontal(|ui| {
+            ui.label("Alive");
+            color_picker::color_edit_button_srgba(
+                ui,
+                &mut self.settings.alive_color,
+                Alpha::Opaque,
+            );
+        });
+        ui.horizontal(|ui| {
+            ui.label("Dead");
+            color_picker::color_edit_button_srgba(ui, &mut self.settings.dead_color, Alpha::Opaque);
+        });
+        ui.horizontal(|ui| {
+            ui.label("Background");
+            color_picker::color_edit_button_srgba(
+                ui,
+                &mut self.settings.background_color,
+                Alpha::Opaque,
+            );
+        });
+
+        ui.separator();
+        ui.heading("Patterns");
+        ComboBox::from_label("Pattern")
+            .selected_text(PATTERNS[self.selected_pattern].name)
+            .show_ui(ui, |ui| {
+                for (idx, pattern) in PATTERNS.iter().enumerate() {
+                    if ui
+                        .selectable_value(&mut self.selected_pattern, idx, pattern.name)
+                        .changed()
+                    {
+                        self.pattern_cells = pattern.cells.to_vec();
+                        self.tool = Tool::Stamp;
+                    }
+                }
+            });
+        self.render_transforms(ui);
+        ui.selectable_value(&mut self.tool, Tool::Stamp, "Stamp (P)")
+            .on_hover_text("Click the canvas to place the pattern; Esc or right-click stops");
+        ui.horizontal(|ui| {
+            if ui.button("Open pattern…").clicked() {
+                self.open_pattern();
+            }
+            if ui
+                .button("Save pattern…")
+                .on_hover_text("Format follows the extension: .rle, .cells or .lif (Life 1.06)")
+                .clicked()
+            {
+                self.save_pattern();
+            }
+        });
+        if ui
+            .button("Copy RLE")
+            .on_hover_text("Copies the whole board; Ctrl+V pastes RLE, plaintext or Life 1.0x")
+            .clicked()
+        {
+            self.copy_rle(ui.ctx());
+        }
+        if let Some(message) = &self.pattern_message {
+            ui.label(message);
+        }
+
+        ui.separator();
+        self.render_selection(ui);
+    }
+
+    /// Tool palette in a toolbar above the canvas, so it stays in reach
+    /// however far the side panel is scrolled.
+    fn render_tools(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal_wrapped(|ui| {
+            for (tool, label) in TOOLS {
+                ui.selectable_value(&mut self.tool, tool, label);
+            }
+            ui.separator();
+            ui.checkbox(
+                &mut self.settings.fill_figures,
+                "Filled rectangles and ellipses",
+            );
+            ui.separator();
+            ui.label("Left button draws, right button erases");
+        });
+    }
+
+    /// Orientation buttons; they act on whatever `transform` would.
+    fn render_transforms(&mut self, ui: &mut egui::Ui) {
+        let target = if self.pasting.is_some() {
+            "the paste preview"
+        } else if self.selection.is_some() && self.tool != Tool::Stamp {
+            "the selection"
+        } else {
+            "the pattern before it is stamped"
+        };
+        ui.horizontal_wrapped(|ui| {
+            for transform in TRANSFORMS {
+                let shortcut = match transform {
+                    Transform::RotateAnticlockwise => " ([)",
+                    Transform::RotateClockwise => " (])",
+                    Transform::FlipHorizontal => " (H)",
+                    Transform::FlipVertical => " (V)",
+                    Transform::Rotate180 => "",
+                };
+                if ui
+                    .small_button(transform.name())
+                    .on_hover_text(format!("Applies to {target}{shortcut}"))
+                    .clicked()
+                {
+                    self.transform(transform);
+                }
+            }
+        });
+    }
+
+    fn render_selection(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Selection");
+        let selected = self.selection.is_some();
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(selected, egui::Button::new("Copy"))
+                .on_hover_text("Ctrl+C; also puts the cells on the system clipboard as RLE")
+                .clicked()
+            {
+                self.copy_selection(ui.ctx());
+            }
+            if ui
+                .add_enabled(selected, egui::Button::new("Cut"))
+                .on_hover_text("Ctrl+X")
+                .clicked()
+            {
+                self.cut_selection(ui.ctx());
+            }
+            if ui
+                .add_enabled(self.clipboard.is_some(), egui::Button::new("Paste"))
+                .on_hover_text("Ctrl+V pastes from the system clipboard")
+                .clicked()
+            {
+                self.start_paste(None);
+            }
+        });
+        ComboBox::from_label("Paste mode")
+            .selected_text(self.settings.paste_mode.name())
+            .show_ui(ui, |ui| {
+                for mode in PASTE_MODES {
+                    ui.selectable_value(&mut self.settings.paste_mode, mode, mode.name());
+                }
+            })
+            .response
+            .on_hover_text("M cycles through the modes");
+        if ui
+            .add_enabled(selected, egui::Button::new("Center on selection"))
+            .clicked()
+        {
+            self.center_on_selection();
+        }
+        if let Some(selection) = self.selection {
+            ui.label(format!(
+                "{} x {} at ({}, {})",
+                selection.width(),
+                selection.height(),
+                selection.left,
+                selection.top
+            ));
+        }
+        if self.pasting.is_some() {
+            ui.label("Click to place, Esc or right-click to cancel");
+        }
+    }
+
+    fn render_soup(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Random soup");
+        ui.horizontal(|ui| {
+            ui.label("Seed");
+            let response =
+                ui.add(egui::TextEdit::singleline(&mut self.seed_input).desired_width(160.0));
+            let submitted =
+                response.lost_focus() && ui.input(|input| input.key_pressed(egui::Key::Enter));
+            if ui
+                .button("Recreate")
+                .on_hover_text("Fill the board again from this seed.")
+                .clicked()
+                || submitted
+            {
+                self.apply_seed_input();
+            }
+        });
+        if let Some(error) = &self.seed_error {
+            ui.colored_label(Color32::LIGHT_RED, error);
+        }
+        ui.add(Slider::new(&mut self.settings.soup.density, 0.0..=1.0).text("Density"));
+        ComboBox::from_label("Symmetry")
+            .selected_text(self.settings.soup.symmetry.name())
+            .show_ui(ui, |ui| {
+                for symmetry in SYMMETRIES {
+                    ui.selectable_value(
+                        &mut self.settings.soup.symmetry,
+                        symmetry,
+                        symmetry.name(),
+                    );
+                }
+            });
+
+        let mut limited = self.settings.soup_size.is_some();
+        if ui
+            .checkbox(&mut limited, "Limit to a centred region")
+            .changed()
+        {
+            self.settings.soup_size = limited.then_some((16, 16));
+        }
+        if let Some((width, height)) = &mut self.settings.soup_size {
+            ui.horizontal(|ui| {
+                ui.add(egui::DragValue::new(width).range(1..=MAX_GRID_SIZE as i64));
+                ui.label("x");
+                ui.add(egui::DragValue::new(height).range(1..=MAX_GRID_SIZE as i64));
+            });
+        }
+    }
+
+    fn render_census(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Census");
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(
+                    self.pending_census.is_none(),
+                    egui::Button::new("Take census"),
+                )
+                .clicked()
+            {
+                self.start_census();
+            }
+            ui.add(Slider::new(&mut self.settings.census_distance, 1..=3).text("Gap"))
+                .on_hover_text("Live cells up to this many cells apart belong to the same object.");
+        });
+
+        if let Some((generation, receiver)) = &self.pending_census {
+            match receiver.try_recv() {
+                Ok(census) => {
+                    self.census = Some((*generation, census));
+                    self.pending_census = None;
+                }
+                Err(mpsc::TryRecvError::Empty) => {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label(format!("Taking census of generation {generation}…"));
+                    });
+                    ui.ctx().request_repaint_after(Duration::from_millis(100));
+                }
+                Err(mpsc::TryRecvError::Disconnected) => self.pending_census = None,
+            }
+        }
+
+        if let Some((generation, census)) = &self.census {
+            ui.label(format!(
+                "{} objects at generation {generation}",
+                census.object_count()
+            ));
+            egui::ScrollArea::vertical()
+                .max_height(160.0)
+                .show(ui, |ui| {
+                    for entry in &census.entries {
+                        ui.label(entry.to_string());
+                    }
+                });
+        }
+    }
+
+    /// Copies the board and classifies its objects on a worker thread; the
+    /// result is picked up by `render_census`.
+    fn start_census(&mut self) {
+        self.sync_plane();
+        let board: Box<dyn Board + Send> = if self.unbounded {
+            Box::new(self.plane.clone())
+        } else {
+            Box::new(self.grid.clone())
+        };
+        let (rule, distance) = (self.rule, self.settings.census_distance);
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = sender.send(take_census(board.as_ref(), &rule, distance));
+        });
+        self.pending_census = Some((self.generation, receiver));
+    }
+
+    fn render_status(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(format!("Generation: {}", self.generation)).strong());
+            ui.separator();
+            if self.unbounded {
+                ui.label(format!("Alive cells: {}", self.alive_cells));
+            } else {
+                ui.label(format!(
+                    "Alive cells: {} / {}",
+                    self.alive_cells,
+                    self.grid.width() * self.grid.height()
+                ));
+            }
+            if let Some(universe) = &self.universe {
+                ui.separator();
+                ui.label(format!("Population (unbounded): {}", universe.population()));
+            }
+            if let Some(stability) = self.cycle.result() {
+                ui.separator();
+                ui.label(RichText::new(self.describe_stability(stability)).strong());
+            }
+            ui.separator();
+            let view = self.view();
+            let edges = if self.unbounded {
+                "unbounded"
+            } else {
+                self.topology.name()
+            };
+            ui.label(format!(
+                "View: {} x {} ({}) | Speed: {} UPS x 2^{} | Rule: {} | Seed: {}",
+                view.width(),
+                view.height(),
+                edges,
+                self.settings.updates_per_second,
+                self.settings.step_exponent,
+                self.rule,
+                self.settings.soup.seed
+            ));
+        });
+        self.render_timeline(ui);
+    }
+
+    fn describe_stability(&self, stability: Stability) -> String {
+        match stability {
+            Stability::Static { since } if self.alive_cells == 0 => {
+                format!("Died out at generation {since}")
+            }
+            Stability::Static { since } => format!("Still life since generation {since}"),
+            Stability::Periodic { period, since } => {
+                format!("Period {period} oscillation since generation {since}")
+            }
+            Stability::Moving { velocity, since } => {
+                format!("{velocity} spaceship since generation {since}")
+            }
+        }
+    }
+
+    fn render_timeline(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(
+                    self.timeline.position() > 0,
+                    egui::Button::new("Step back (,)"),
+                )
+                .clicked()
+            {
+                self.step_back();
+            }
+
+            let last = self.timeline.frame_count().saturating_sub(1);
+            let mut index = self.timeline.position();
+            let response = ui.add_enabled(
+                last > 0,
+                Slider::new(&mut index, 0..=last).show_value(false),
+            );
+            if response.changed() {
+                self.show_frame(index);
+            }
+
+            let oldest = self.timeline.frame(0).map_or(0, |frame| frame.generation);
+            ui.label(format!(
+                "Timeline: generation {} of {}-{} ({} frames, {:.1} MB)",
+                self.generation,
+                oldest,
+                self.timeline
+                    .frame(last)
+                    .map_or(0, |frame| frame.generation),
+                self.timeline.frame_count(),
+                self.timeline.memory_usage() as f64 / (1024.0 * 1024.0)
+            ));
+        });
+    }
+
+    fn render_canvas(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        let view = self.view();
+        if view.width() <= 0 || view.height() <= 0 {
+            return;
+        }
+        let (response, painter) = ui.allocate_painter(ui.available_size(), Sense::click_and_drag());
+        let rect = response.rect;
+        self.canvas_size = rect.size();
+        if self.camera_follows_view {
+            self.camera.fit(view, rect.size());
+        }
+        self.handle_camera_input(&response, ctx);
+        self.refresh_plane_window();
+
+        painter.rect_filled(rect, 0.0, self.settings.background_color);
+        let board_rect = self.screen_rect(rect.min, view);
+        let max_side = ctx.input(|input| input.max_texture_side);
+        match self.camera.texture_region(rect.size(), view, max_side) {
+            Some(region) => {
+                let texture = self.canvas_texture(ctx, region);
+                painter.image(
+                    texture,
+                    self.screen_rect(rect.min, region),
+                    egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+                    Color32::WHITE,
+                );
+            }
+            None => self.paint_live_cells(&painter, rect, view),
+        }
+
+        // Lines would cover the cells once they are only a few pixels wide.
+        if self.camera.scale >= 4.0 {
+            let visible = self.camera.visible(rect.size());
+            let stroke = egui::Stroke::new(0.5, self.settings.grid_line_color);
+            let (top, bottom) = (board_rect.top(), board_rect.bottom());
+            for column in visible.left.max(view.left)..=visible.right.min(view.right) {
+                let x = rect.left() + self.camera.cell_to_screen(column, 0).x;
+                painter.line_segment([egui::pos2(x, top), egui::pos2(x, bottom)], stroke);
+            }
+            let (left, right) = (board_rect.left(), board_rect.right());
+            for row in visible.top.max(view.top)..=visible.bottom.min(view.bottom) {
+                let y = rect.top() + self.camera.cell_to_screen(0, row).y;
+                painter.line_segment([egui::pos2(left, y), egui::pos2(right, y)], stroke);
+            }
+        }
+
+        self.render_overlays(&response, &painter);
+        self.handle_pointer_input(&response, ctx);
+    }
+
+    /// Outlines the selection and draws the figure being dragged or the paste
+    /// or stamp preview under the cursor.
+    fn render_overlays(&self, response: &egui::Response, painter: &egui::Painter) {
+        let origin = response.rect.min.to_vec2();
+        let cell_rect = |bounds: Bounds| {
+            egui::Rect::from_min_max(
+                self.camera.cell_to_screen(bounds.left, bounds.top) + origin,
+                self.camera.cell_to_screen(bounds.right, bounds.bottom) + origin,
+            )
+        };
+        let highlight = Color32::from_rgb(0x4a, 0x9e, 0xff);
+
+        if let Some(selection) = self.selection {
+            painter.rect_stroke(cell_rect(selection), 0.0, egui::Stroke::new(1.5, highlight));
+        }
+
+        let Some(hover) = response.hover_pos() else {
+            return;
+        };
+        let (x, y) = self.camera.screen_to_cell(hover - origin);
+
+        if let (Tool::Figure(figure), Some((start, cell))) = (self.tool, self.figure_start) {
+            let color = if cell.is_alive() {
+                self.settings.alive_color.gamma_multiply(0.5)
+            } else {
+                self.settings.dead_color
+            };
+            for (cx, cy) in figure.cells(start, (x, y), self.settings.fill_figures) {
+                painter.rect_filled(cell_rect(Bounds::new(cx, cy, 1, 1)), 0.0, color);
+            }
+            return;
+        }
+
+        let stamp;
+        let (clip, left, top) = if let Some(clip) = &self.pasting {
+            (clip, x, y)
+        } else if self.tool == Tool::Stamp {
+            stamp = self.stamp_clip();
+            (&stamp, x - stamp.width / 2, y - stamp.height / 2)
+        } else {
+            return;
+        };
+        let ghost = self.settings.alive_color.gamma_multiply(0.5);
+        for &(dx, dy) in &clip.cells {
+            painter.rect_filled(
+                cell_rect(Bounds::new(left + dx, top + dy, 1, 1)),
+                0.0,
+                ghost,
+            );
+        }
+        painter.rect_stroke(
+            cell_rect(Bounds::new(left, top, clip.width, clip.height)),
+            0.0,
+            egui::Stroke::new(1.0, highlight),
+        );
+    }
+
+    /// Middle-drag or Space-drag pans and the wheel zooms around the cursor;
+    /// either stops the camera from fitting the board automatically.
+    fn handle_camera_input(&mut self, response: &egui::Response, ctx: &egui::Context) {
+        let space_down = ctx.input(|input| input.key_down(egui::Key::Space));
+        if response.dragged_by(egui::PointerButton::Middle)
+            || (space_down && response.dragged_by(egui::PointerButton::Primary))
+        {
+            self.camera.pan(response.drag_delta());
+            self.camera_follows_view = false;
+            self.space_panned |= space_down;
+        }
+
+        if let Some(hover) = response.hover_pos() {
+            let (scroll, zoom) =
+                ctx.input(|input| (input.smooth_scroll_delta.y, input.zoom_delta()));
+            let factor = zoom * (scroll * ZOOM_PER_SCROLL_POINT).exp();
+            if factor != 1.0 {
+                let anchor = (hover - response.rect.min).to_pos2();
+                self.camera.zoom_at(factor, anchor);
+                self.camera_follows_view = false;
+            }
+        }
+    }
+
+    /// Zooms to the live cells, or the whole board when it is empty.
+    fn fit_to_pattern(&mut self) {
+        let bounds = self.pattern_bounds().unwrap_or(self.view());
+        self.camera.fit(bounds, self.canvas_size);
+        self.camera_follows_view = false;
+    }
+
+    fn center_on_pattern(&mut self) {
+        let bounds = self.pattern_bounds().unwrap_or(self.view());
+        self.camera.center_on(bounds, self.canvas_size);
+        self.camera_follows_view = false;
+    }
+
+    /// Dragging spans a selection from the press to the pointer; a click
+    /// without dragging clears it.
+    fn handle_selection_input(&mut self, response: &egui::Response, cell: Option<(i64, i64)>) {
+        let Some((x, y)) = cell else {
+            return;
+        };
+        if response.drag_started_by(egui::PointerButton::Primary) {
+            self.selection_anchor = Some((x, y));
+        }
+        if response.dragged_by(egui::PointerButton::Primary) {
+            if let Some((anchor_x, anchor_y)) = self.selection_anchor {
+                let spanned = Bounds::new(anchor_x, anchor_y, 1, 1).including(x, y);
+                self.selection = Some(if self.unbounded {
+                    spanned
+                } else {
+                    let view = self.view();
+                    Bounds {
+                        left: spanned.left.max(view.left),
+                        top: spanned.top.max(view.top),
+                        right: spanned.right.min(view.right),
+                        bottom: spanned.bottom.min(view.bottom),
+                    }
+                })
+                .filter(|bounds| bounds.width() > 0 && bounds.height() > 0);
+            }
+        } else if response.clicked() {
+            self.selection = None;
+        }
+        if response.drag_stopped() {
+            self.selection_anchor = None;
+        }
+    }
+
+    /// Canvas rectangle covering `bounds`, for a canvas whose top-left corner
+    /// is `origin`.
+    fn screen_rect(&self, origin: egui::Pos2, bounds: Bounds) -> egui::Rect {
+        egui::Rect::from_min_max(
+            origin
+                + self
+                    .camera
+                    .cell_to_screen(bounds.left, bounds.top)
+                    .to_vec2(),
+            origin
+                + self
+                    .camera
+                    .cell_to_screen(bounds.right, bounds.bottom)
+                    .to_vec2(),
+        )
+    }
+
+    /// Draws the visible live cells one by one, at least a pixel wide, for
+    /// views too large to rasterise into a texture.
+    fn paint_live_cells(&self, painter: &egui::Painter, rect: egui::Rect, view: Bounds) {
+        let Some(visible) = self.camera.visible(rect.size()).intersection(&view) else {
+            return;
+        };
+        painter.rect_filled(
+            self.screen_rect(rect.min, visible).intersect(rect),
+            0.0,
+            self.settings.dead_color,
+        );
+        let side = self.camera.scale.max(1.0);
+        for (x, y) in self.board().live_cells() {
+            if visible.contains(x, y) {
+                let corner = rect.min + self.camera.cell_to_screen(x, y).to_vec2();
+                painter.rect_filled(
+                    egui::Rect::from_min_size(corner, egui::vec2(side, side)),
+                    0.0,
+                    self.settings.alive_color,
+                );
+            }
+        }
+    }
+
+    /// The board as a texture with one pixel per cell of `region`, redrawn
+    /// only after the board, region or colors changed.
+    fn canvas_texture(&mut self, ctx: &egui::Context, region: Bounds) -> egui::TextureId {
+        let key = (
+            self.board_revision,
+            region,
+            self.settings.alive_color,
+            self.settings.dead_color,
+        );
+        if let Some((cached, texture)) = &self.canvas {
+            if *cached == key {
+                return texture.id();
+            }
+        }
+
+        let (width, height) = (region.width() as usize, region.height() as usize);
+        let mut image = egui::ColorImage::new([width, height], self.settings.dead_color);
+        for (x, y) in self.board().live_cells() {
+            if region.contains(x, y) {
+                let index = (y - region.top) as usize * width + (x - region.left) as usize;
+                image.pixels[index] = self.settings.alive_color;
+            }
+        }
+        let options = egui::TextureOptions {
+            magnification: egui::TextureFilter::Nearest,
+            ..egui::TextureOptions::LINEAR
+        };
+        let texture = match self.canvas.take() {
+            Some((_, mut texture)) => {
+                texture.set(image, options);
+                texture
+            }
+            None => ctx.load_texture("board", image, options),
+        };
+        let id = texture.id();
+        self.canvas = Some((key, texture));
+        id
+    }
+
+    fn handle_pointer_input(&mut self, response: &egui::Response, ctx: &egui::Context) {
+        let (primary_down, secondary_down, panning) = ctx.input(|input| {
+            (
+                input.pointer.primary_down(),
+                input.pointer.secondary_down(),
+                input.key_down(egui::Key::Space) || input.pointer.middle_down(),
+            )
+        });
+        if panning {
+            self.stroke_active = false;
+            self.last_painted = None;
+            self.figure_start = None;
+            return;
+        }
+        let hovered_cell = response
+            .hover_pos()
+            .or(response.interact_pointer_pos())
+            .map(|position| {
+                self.camera
+                    .screen_to_cell((position - response.rect.min).to_pos2())
+            });
+
+        if self.pasting.is_some() {
+            if response.clicked() {
+                if let Some((x, y)) = hovered_cell {
+                    self.place_paste(x, y);
+                }
+            } else if response.secondary_clicked() {
+                self.pasting = None;
+            }
+            return;
+        }
+
+        if self.tool == Tool::Select {
+            self.handle_selection_input(response, hovered_cell);
+            return;
+        }
+
+        if self.tool == Tool::Stamp {
+            if response.clicked() {
+                if let Some((x, y)) = hovered_cell {
+                    self.place_stamp(x, y);
+                }
+            } else if response.secondary_clicked() {
+                self.tool = Tool::Draw;
+            }
+            return;
+        }
+
+        if let Tool::Figure(figure) = self.tool {
+            self.handle_figure_input(response, hovered_cell, figure);
+            return;
+        }
+
+        if self.tool == Tool::Fill {
+            if let Some(start) = hovered_cell {
+                if response.clicked() {
+                    self.fill_region(start, Cell::Alive);
+                } else if response.secondary_clicked() {
+                    self.fill_region(start, Cell::Dead);
+                }
+            }
+            return;
+        }
+
+        if !primary_down && !secondary_down {
+            self.stroke_active = false;
+            self.last_painted = None;
+            return;
+        }
+
+        if let Some(pointer_pos) = response.interact_pointer_pos() {
+            if !response.rect.contains(pointer_pos) {
+                return;
+            }
+
+            let (x, y) = self
+                .camera
+                .screen_to_cell((pointer_pos - response.rect.min).to_pos2());
+
+            // Join up with the last frame so fast drags leave no gaps.
+            let cells = match self.last_painted {
+                Some(previous) => line(previous, (x, y)),
+                None => vec![(x, y)],
+            };
+            self.last_painted = Some((x, y));
+            let view = self.view();
+            if !self.unbounded && !cells.iter().any(|&(x, y)| view.contains(x, y)) {
+                return;
+            }
+            if !self.stroke_active {
+                self.record_history();
+                self.stroke_active = true;
+            }
+            let cell = if primary_down {
+                Cell::Alive
+            } else {
+                Cell::Dead
+            };
+            if self.paint(&cells, cell) {
+                self.grid_edited();
+            }
+        }
+    }
+
+    /// Sets `cells` to `cell`, skipping those off a bounded grid; the
+    /// unbounded plane can be painted anywhere and the view follows. Returns
+    /// whether any cell was written.
+    fn paint(&mut self, cells: &[(i64, i64)], cell: Cell) -> bool {
+        let view = self.view();
+        let unbounded = self.unbounded;
+        let board = self.board_mut();
+        let mut painted = false;
+        for &(x, y) in cells {
+            if unbounded || view.contains(x, y) {
+                board.set(x, y, cell);
+                painted = true;
+            }
+        }
+        if painted && unbounded {
+            self.follow_pattern();
+        }
+        painted
+    }
+
+    /// A drag with either button previews the figure; releasing draws it as a
+    /// single undoable edit.
+    fn handle_figure_input(
+        &mut self,
+        response: &egui::Response,
+        cell: Option<(i64, i64)>,
+        figure: Figure,
+    ) {
+        let Some(end) = cell else {
+            return;
+        };
+        if response.drag_started_by(egui::PointerButton::Primary) {
+            self.figure_start = Some((end, Cell::Alive));
+        } else if response.drag_started_by(egui::PointerButton::Secondary) {
+            self.figure_start = Some((end, Cell::Dead));
+        }
+        if !response.drag_stopped() {
+            return;
+        }
+        let Some((start, state)) = self.figure_start.take() else {
+            return;
+        };
+        let cells = figure.cells(start, end, self.settings.fill_figures);
+        self.record_history();
+        if self.paint(&cells, state) {
+            self.grid_edited();
+        }
+    }
+
+    /// Floods the region around `start` with `cell`, within the board or, on
+    /// the unbounded plane, the view.
+    fn fill_region(&mut self, start: (i64, i64), cell: Cell) {
+        if self.board().get(start.0, start.1) == cell {
+            return;
+        }
+        let Some(region) = flood_fill(self.board(), start, self.view(), MAX_FILL_CELLS) else {
+            self.pattern_message = Some(format!(
+                "Cannot fill: the region has more than {MAX_FILL_CELLS} cells"
+            ));
+            return;
+        };
+        if region.is_empty() {
+            return;
+        }
+        self.record_history();
+        self.paint(&region, cell);
+        self.grid_edited();
+    }
+}
+
+impl eframe::App for GameApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.handle_keyboard(ctx);
+        self.maybe_step_simulation();
+
+        egui::TopBottomPanel::top("tool_bar").show(ctx, |ui| {
+            self.render_tools(ui);
+        });
+
+        egui::SidePanel::left("control_panel")
+            .resizable(false)
+            .default_width(260.0)
+            .show(ctx, |ui| {
+                // The controls are taller than a default window.
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    self.render_controls(ui);
+                });
+            });
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            self.render_canvas(ui, ctx);
+        });
+
+        egui::TopBottomPanel::bottom("status_panel").show(ctx, |ui| {
+            self.render_status(ui);
+        });
+
+        // Input events repaint on their own; only a running simulation needs
+        // to wake the UI for its next step.
+        if !self.paused {
+            let step = self.settings.step_duration();
+            ctx.request_repaint_after(step.saturating_sub(self.last_step.elapsed()));
+        }
     }
 }
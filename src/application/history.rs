@@ -0,0 +1,69 @@
+use std::collections::VecDeque;
+
+/// Undo/redo stack of snapshots. Each entry carries an estimated size in
+/// bytes, and the oldest undo entries are dropped once the total exceeds the
+/// budget.
+pub struct History<T> {
+    undo: VecDeque<(T, usize)>,
+    redo: Vec<(T, usize)>,
+    budget: usize,
+    used: usize,
+}
+
+impl<T> History<T> {
+    pub fn new(budget: usize) -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            budget,
+            used: 0,
+        }
+    }
+
+    /// Saves the state from before an action and forgets anything undone.
+    pub fn record(&mut self, state: T, cost: usize) {
+        self.used -= self.redo.drain(..).map(|(_, cost)| cost).sum::<usize>();
+        self.undo.push_back((state, cost));
+        self.used += cost;
+        self.trim();
+    }
+
+    /// Swaps `current` for the most recently recorded state.
+    pub fn undo(&mut self, current: T, cost: usize) -> Option<T> {
+        let (state, state_cost) = self.undo.pop_back()?;
+        self.redo.push((current, cost));
+        self.used = self.used - state_cost + cost;
+        self.trim();
+        Some(state)
+    }
+
+    /// Swaps `current` for the most recently undone state.
+    pub fn redo(&mut self, current: T, cost: usize) -> Option<T> {
+        let (state, state_cost) = self.redo.pop()?;
+        self.undo.push_back((current, cost));
+        self.used = self.used - state_cost + cost;
+        self.trim();
+        Some(state)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    fn trim(&mut self) {
+        while self.used > self.budget {
+            match self.undo.pop_front() {
+                Some((_, cost)) => self.used -= cost,
+                None => break,
+            }
+        }
+    }
+}
+
+#[path = "history_test.rs"]
+#[cfg(test)]
+mod history_test;
@@ -0,0 +1,43 @@
+use super::History;
+
+#[test]
+fn undo_and_redo_walk_through_recorded_states() {
+    let mut history = History::new(100);
+    history.record("empty", 1);
+    history.record("glider", 1);
+
+    assert_eq!(history.undo("glider moved", 1), Some("glider"));
+    assert_eq!(history.undo("glider", 1), Some("empty"));
+    assert_eq!(history.undo("empty", 1), None);
+    assert!(!history.can_undo());
+
+    assert_eq!(history.redo("empty", 1), Some("glider"));
+    assert_eq!(history.redo("glider", 1), Some("glider moved"));
+    assert_eq!(history.redo("glider moved", 1), None);
+}
+
+#[test]
+fn recording_discards_the_redo_branch() {
+    let mut history = History::new(100);
+    history.record(1, 1);
+    assert_eq!(history.undo(2, 1), Some(1));
+    assert!(history.can_redo());
+
+    history.record(1, 1);
+
+    assert!(!history.can_redo());
+    assert_eq!(history.undo(3, 1), Some(1));
+}
+
+#[test]
+fn oldest_states_are_dropped_over_budget() {
+    let mut history = History::new(10);
+    for state in 0..5 {
+        history.record(state, 4);
+    }
+
+    assert_eq!(history.undo(5, 4), Some(4));
+    assert_eq!(history.undo(4, 4), Some(3));
+    assert_eq!(history.undo(3, 4), None);
+    assert!(history.can_redo());
+}
@@ -1,3 +1,9 @@
+pub mod camera;
+pub mod cli;
 pub mod game_app;
+pub mod history;
+pub mod search;
+pub mod timeline;
+pub mod tui;
 
 pub use game_app::GameApp;
@@ -0,0 +1,327 @@
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::process::ExitCode;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Instant;
+
+use super::cli::CliError;
+use crate::domain::census::CensusEntry;
+use crate::domain::{
+    next_generation_unbounded, take_census, Board, Bounds, Census, Rule, Soup, SparseGrid,
+    SYMMETRIES,
+};
+
+/// Longest population cycle recognised as settled; 60 covers mixes of the
+/// common periods 2, 3, 4, 5 and 15.
+const MAX_SETTLE_PERIOD: usize = 60;
+/// A population cycle must repeat this many times before the soup counts as
+/// settled, so brief plateaus during the evolution are not mistaken for it.
+const SETTLE_REPEATS: usize = 4;
+/// Shortest stretch of repeating population accepted as settled.
+const MIN_SETTLE_SPAN: usize = 30;
+/// Gap used to split the settled soup into objects; the census then splits
+/// pieces within it that do not interact.
+const CENSUS_DISTANCE: i64 = 2;
+/// Objects too common to be worth logging.
+const COMMON_OBJECTS: [&str; 13] = [
+    "block",
+    "blinker",
+    "beehive",
+    "glider",
+    "loaf",
+    "boat",
+    "ship",
+    "tub",
+    "pond",
+    "long boat",
+    "toad",
+    "beacon",
+    "barge",
+];
+
+const USAGE: &str = "\
+usage: game_of_life search [options]
+
+options:
+  -n, --soups <N>          soups to run (default 1000)
+  -s, --seed <SEED>        seed of the first soup; soup i uses SEED + i (default random)
+      --size <N>           side of the square soup (default 16)
+  -d, --density <D>        probability of each cell being alive (default 0.3)
+  -y, --symmetry <SYM>     C1, C2, C4, D2, D4 or D8 (default C1)
+  -r, --rule <RULE>        rule to evolve, e.g. B36/S23 (default B3/S23)
+  -g, --max-generations <N> give up on soups still active after N generations (default 10000)
+  -m, --methuselah <N>     log soups that take at least N generations to settle (default 2500)
+  -o, --output <FILE>      log file, appended to (default soup_search.log)
+  -j, --threads <N>        worker threads (default: all cores)";
+
+/// Settings for a headless soup search, parsed from the command line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchOptions {
+    pub soups: u64,
+    /// Density, symmetry and the seed of the first soup.
+    pub soup: Soup,
+    pub size: i64,
+    pub rule: Rule,
+    pub max_generations: u64,
+    pub methuselah: u64,
+    pub output: String,
+    pub threads: usize,
+}
+
+/// What became of one soup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SoupResult {
+    pub seed: u64,
+    /// Generation the soup settled at, or `None` if it was still active
+    /// after the generation limit.
+    pub lifespan: Option<u64>,
+    pub census: Census,
+}
+
+impl SoupResult {
+    /// Why the soup deserves a log entry, if it does.
+    pub fn interest(&self, options: &SearchOptions) -> Option<String> {
+        let mut reasons = Vec::new();
+        match self.lifespan {
+            None => reasons.push(format!(
+                "still active after {} generations",
+                options.max_generations
+            )),
+            Some(lifespan) if lifespan >= options.methuselah => {
+                reasons.push(format!("methuselah lasting {lifespan} generations"))
+            }
+            Some(_) => {}
+        }
+        let rare: Vec<String> = self
+            .census
+            .entries
+            .iter()
+            .filter(|entry| !COMMON_OBJECTS.contains(&entry.name.as_str()))
+            .map(|entry| entry.to_string())
+            .collect();
+        if !rare.is_empty() {
+            reasons.push(format!("rare: {}", rare.join(", ")));
+        }
+        (!reasons.is_empty()).then(|| reasons.join("; "))
+    }
+}
+
+/// Entry point for `game_of_life search …`; `args` excludes the subcommand.
+pub fn main(args: &[String]) -> ExitCode {
+    let options = match parse_args(args) {
+        Ok(options) => options,
+        Err(err) => {
+            match &err {
+                CliError::Usage(message) => eprintln!("error: {message}\n\n{USAGE}"),
+                _ => eprintln!("error: {err}"),
+            }
+            return ExitCode::from(err.exit_code());
+        }
+    };
+
+    match search(&options) {
+        Ok(summary) => {
+            println!("{summary}");
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::from(err.exit_code())
+        }
+    }
+}
+
+pub fn parse_args(args: &[String]) -> Result<SearchOptions, CliError> {
+    let mut options = SearchOptions {
+        soups: 1000,
+        soup: Soup {
+            seed: rand::random(),
+            ..Soup::default()
+        },
+        size: 16,
+        rule: Rule::default(),
+        max_generations: 10_000,
+        methuselah: 2500,
+        output: "soup_search.log".to_string(),
+        threads: thread::available_parallelism().map_or(1, |count| count.get()),
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let value = args
+            .next()
+            .ok_or_else(|| CliError::Usage(format!("missing value for '{arg}'")))?;
+        let invalid = || CliError::Usage(format!("invalid value '{value}' for '{arg}'"));
+        match arg.as_str() {
+            "-n" | "--soups" => options.soups = value.parse().map_err(|_| invalid())?,
+            "-s" | "--seed" => options.soup.seed = value.parse().map_err(|_| invalid())?,
+            "--size" => {
+                options.size = value.parse().map_err(|_| invalid())?;
+                if options.size < 1 {
+                    return Err(invalid());
+                }
+            }
+            "-d" | "--density" => {
+                options.soup.density = value.parse().map_err(|_| invalid())?;
+                if !(0.0..=1.0).contains(&options.soup.density) {
+                    return Err(invalid());
+                }
+            }
+            "-y" | "--symmetry" => {
+                options.soup.symmetry = SYMMETRIES
+                    .into_iter()
+                    .find(|symmetry| {
+                        symmetry
+                            .name()
+                            .split_whitespace()
+                            .next()
+                            .is_some_and(|short| short.eq_ignore_ascii_case(value))
+                    })
+                    .ok_or_else(invalid)?
+            }
+            "-r" | "--rule" => {
+                options.rule = Rule::parse(value)
+                    .map_err(|err| CliError::Usage(format!("invalid rule '{value}': {err}")))?
+            }
+            "-g" | "--max-generations" => {
+                options.max_generations = value.parse().map_err(|_| invalid())?
+            }
+            "-m" | "--methuselah" => options.methuselah = value.parse().map_err(|_| invalid())?,
+            "-o" | "--output" => options.output = value.clone(),
+            "-j" | "--threads" => {
+                options.threads = value.parse().map_err(|_| invalid())?;
+                if options.threads == 0 {
+                    return Err(invalid());
+                }
+            }
+            _ => return Err(CliError::Usage(format!("unknown option '{arg}'"))),
+        }
+    }
+    Ok(options)
+}
+
+/// Runs every soup across the worker threads, appends interesting results
+/// to the log as they arrive and returns a summary with the total census.
+pub fn search(options: &SearchOptions) -> Result<String, CliError> {
+    let io_error = |err: std::io::Error| CliError::Io(format!("{}: {err}", options.output));
+    let mut log = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&options.output)
+        .map_err(io_error)?;
+    writeln!(
+        log,
+        "# {} soups from seed {}: {size}x{size}, density {}, {}, rule {}",
+        options.soups,
+        options.soup.seed,
+        options.soup.density,
+        options.soup.symmetry.name(),
+        options.rule,
+        size = options.size,
+    )
+    .map_err(io_error)?;
+
+    let started = Instant::now();
+    let next = AtomicU64::new(0);
+    let (sender, receiver) = mpsc::channel();
+    let mut totals: HashMap<(String, String), usize> = HashMap::new();
+    let mut logged = 0;
+
+    thread::scope(|scope| -> Result<(), CliError> {
+        for _ in 0..options.threads {
+            let sender = sender.clone();
+            let next = &next;
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                if index >= options.soups {
+                    break;
+                }
+                let seed = options.soup.seed.wrapping_add(index);
+                if sender.send(search_soup(seed, options)).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        for result in receiver {
+            for entry in &result.census.entries {
+                *totals
+                    .entry((entry.name.clone(), entry.plural.clone()))
+                    .or_default() += entry.count;
+            }
+            if let Some(interest) = result.interest(options) {
+                writeln!(log, "seed {}: {interest} ({})", result.seed, result.census)
+                    .map_err(io_error)?;
+                logged += 1;
+            }
+        }
+        Ok(())
+    })?;
+
+    let mut entries: Vec<CensusEntry> = totals
+        .into_iter()
+        .map(|((name, plural), count)| CensusEntry {
+            name,
+            plural,
+            count,
+        })
+        .collect();
+    entries.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+    Ok(format!(
+        "Searched {} soups in {:.1}s; {logged} logged to {}\nTotal census: {}",
+        options.soups,
+        started.elapsed().as_secs_f64(),
+        options.output,
+        Census { entries }
+    ))
+}
+
+/// Fills a soup from `seed` on the unbounded plane, evolves it until it
+/// settles and takes its census.
+pub fn search_soup(seed: u64, options: &SearchOptions) -> SoupResult {
+    let mut plane = SparseGrid::new();
+    let soup = Soup {
+        seed,
+        ..options.soup
+    };
+    soup.fill(&mut plane, Bounds::new(0, 0, options.size, options.size));
+    let (plane, lifespan) = settle(plane, &options.rule, options.max_generations);
+    SoupResult {
+        seed,
+        lifespan,
+        census: take_census(&plane, &options.rule, CENSUS_DISTANCE),
+    }
+}
+
+/// Evolves `plane` until its population has cycled `SETTLE_REPEATS` times
+/// (and for at least `MIN_SETTLE_SPAN` generations),
+/// returning the final board and the generation the cycle began at. Escaping
+/// spaceships keep the population constant, so they do not delay settling.
+fn settle(mut plane: SparseGrid, rule: &Rule, max_generations: u64) -> (SparseGrid, Option<u64>) {
+    let mut populations = vec![plane.population()];
+    for generation in 1..=max_generations {
+        plane = next_generation_unbounded(&plane, rule);
+        populations.push(plane.population());
+        let last = populations.len() - 1;
+        for period in 1..=MAX_SETTLE_PERIOD {
+            let span = (period * (SETTLE_REPEATS + 1)).max(MIN_SETTLE_SPAN);
+            if span > last {
+                break;
+            }
+            if (last + 1 - span + period..=last)
+                .all(|index| populations[index] == populations[index - period])
+            {
+                return (plane, Some(generation + 1 - span as u64));
+            }
+        }
+    }
+    (plane, None)
+}
+
+#[path = "search_test.rs"]
+#[cfg(test)]
+mod search_test;
@@ -0,0 +1,151 @@
+use super::{parse_args, search_soup, settle, SearchOptions, SoupResult};
+use crate::application::cli::CliError;
+use crate::domain::census::CensusEntry;
+use crate::domain::soup::Symmetry;
+use crate::domain::{take_census, Board, Cell, Census, Rule, SparseGrid};
+
+fn args(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+fn options() -> SearchOptions {
+    parse_args(&args(&["--seed", "1", "-j", "1"])).unwrap()
+}
+
+fn result(lifespan: Option<u64>, names: &[&str]) -> SoupResult {
+    SoupResult {
+        seed: 0,
+        lifespan,
+        census: Census {
+            entries: names
+                .iter()
+                .map(|name| CensusEntry {
+                    name: name.to_string(),
+                    plural: format!("{name}s"),
+                    count: 1,
+                })
+                .collect(),
+        },
+    }
+}
+
+#[test]
+fn parse_args_reads_every_option() {
+    let options = parse_args(&args(&[
+        "-n", "50", "--seed", "7", "--size", "20", "-d", "0.5", "-y", "d8", "-r", "b36/s23", "-g",
+        "500", "-m", "300", "-o", "out.log", "-j", "3",
+    ]))
+    .unwrap();
+
+    assert_eq!(options.soups, 50);
+    assert_eq!(options.soup.seed, 7);
+    assert_eq!(options.size, 20);
+    assert_eq!(options.soup.density, 0.5);
+    assert_eq!(options.soup.symmetry, Symmetry::D8);
+    assert_eq!(Some(options.rule), Rule::parse("B36/S23").ok());
+    assert_eq!(options.max_generations, 500);
+    assert_eq!(options.methuselah, 300);
+    assert_eq!(options.output, "out.log");
+    assert_eq!(options.threads, 3);
+}
+
+#[test]
+fn parse_args_rejects_bad_values() {
+    for bad in [
+        &["-d", "1.5"][..],
+        &["-y", "c3"],
+        &["-j", "0"],
+        &["--size", "0"],
+        &["--bogus", "1"],
+        &["-n"],
+    ] {
+        assert!(
+            matches!(parse_args(&args(bad)), Err(CliError::Usage(_))),
+            "{bad:?}"
+        );
+    }
+}
+
+#[test]
+fn settle_reports_when_the_r_pentomino_stabilises() {
+    let mut plane = SparseGrid::new();
+    for (x, y) in [(1, 0), (2, 0), (0, 1), (1, 1), (1, 2)] {
+        plane.set(x, y, Cell::Alive);
+    }
+
+    let (plane, lifespan) = settle(plane, &Rule::default(), 2000);
+
+    let lifespan = lifespan.unwrap();
+    assert!((1090..=1110).contains(&lifespan), "{lifespan}");
+    assert_eq!(plane.population(), 116);
+}
+
+#[test]
+fn settle_gives_up_at_the_generation_limit() {
+    let mut plane = SparseGrid::new();
+    for (x, y) in [(1, 0), (2, 0), (0, 1), (1, 1), (1, 2)] {
+        plane.set(x, y, Cell::Alive);
+    }
+
+    assert_eq!(settle(plane, &Rule::default(), 100).1, None);
+}
+
+#[test]
+fn search_soup_is_reproducible_from_its_seed() {
+    let options = options();
+
+    let first = search_soup(42, &options);
+
+    assert_eq!(first.seed, 42);
+    assert!(first.lifespan.is_some());
+    assert_eq!(search_soup(42, &options), first);
+}
+
+#[test]
+fn interest_flags_methuselahs_unsettled_soups_and_rare_objects() {
+    let options = options();
+
+    assert_eq!(
+        result(Some(10), &["block", "glider"]).interest(&options),
+        None
+    );
+    assert_eq!(
+        result(Some(3000), &["block"]).interest(&options),
+        Some("methuselah lasting 3000 generations".to_string())
+    );
+    assert_eq!(
+        result(None, &[]).interest(&options),
+        Some("still active after 10000 generations".to_string())
+    );
+    assert_eq!(
+        result(
+            Some(10),
+            &["block", "still life (8 cells)", "pentadecathlon"]
+        )
+        .interest(&options),
+        Some("rare: 1 still life (8 cells), 1 pentadecathlon".to_string())
+    );
+}
+
+#[test]
+fn unlisted_still_lifes_are_logged() {
+    let mut plane = SparseGrid::new();
+    // Eater 1, a still life missing from the census table, within the census
+    // gap of a block.
+    for (x, y) in [(0, 0), (1, 0), (0, 1), (2, 1), (2, 2), (2, 3), (3, 3)] {
+        plane.set(x, y, Cell::Alive);
+    }
+    for (x, y) in [(5, 0), (6, 0), (5, 1), (6, 1)] {
+        plane.set(x, y, Cell::Alive);
+    }
+    let result = SoupResult {
+        seed: 0,
+        lifespan: Some(100),
+        census: take_census(&plane, &Rule::conway(), 2),
+    };
+
+    assert_eq!(
+        result.interest(&options()),
+        Some("rare: 1 still life (7 cells)".to_string())
+    );
+}
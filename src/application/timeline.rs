@@ -0,0 +1,92 @@
+use std::collections::VecDeque;
+use std::mem;
+
+/// Live cells of the board at one generation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub generation: u64,
+    pub cells: Vec<(i64, i64)>,
+}
+
+impl Frame {
+    fn cost(&self) -> usize {
+        mem::size_of::<Frame>() + self.cells.len() * mem::size_of::<(i64, i64)>()
+    }
+}
+
+/// Ring buffer of recent generations that can be stepped back through or
+/// scrubbed. The oldest frames are dropped once the budget is exceeded.
+pub struct Timeline {
+    frames: VecDeque<Frame>,
+    /// Index of the frame currently on the board.
+    position: usize,
+    budget: usize,
+    used: usize,
+}
+
+impl Timeline {
+    pub fn new(budget: usize) -> Self {
+        Self {
+            frames: VecDeque::new(),
+            position: 0,
+            budget,
+            used: 0,
+        }
+    }
+
+    /// Starts a new timeline at `frame`, e.g. after the board was edited.
+    pub fn reset(&mut self, frame: Frame) {
+        self.frames.clear();
+        self.used = 0;
+        self.position = 0;
+        self.push(frame);
+    }
+
+    /// Appends the next generation, discarding any frames after the current
+    /// position since they no longer follow from it.
+    pub fn push(&mut self, frame: Frame) {
+        if !self.frames.is_empty() {
+            for dropped in self.frames.drain(self.position + 1..) {
+                self.used -= dropped.cost();
+            }
+        }
+        self.used += frame.cost();
+        self.frames.push_back(frame);
+        self.position = self.frames.len() - 1;
+
+        while self.used > self.budget && self.frames.len() > 1 {
+            if let Some(dropped) = self.frames.pop_front() {
+                self.used -= dropped.cost();
+                self.position -= 1;
+            }
+        }
+    }
+
+    /// Moves to the frame at `index` and returns it.
+    pub fn seek(&mut self, index: usize) -> Option<&Frame> {
+        let frame = self.frames.get(index)?;
+        self.position = index;
+        Some(frame)
+    }
+
+    pub fn frame(&self, index: usize) -> Option<&Frame> {
+        self.frames.get(index)
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Approximate bytes held by the retained frames.
+    pub fn memory_usage(&self) -> usize {
+        self.used
+    }
+}
+
+#[path = "timeline_test.rs"]
+#[cfg(test)]
+mod timeline_test;
@@ -0,0 +1,67 @@
+use super::{Frame, Timeline};
+
+fn frame(generation: u64, population: usize) -> Frame {
+    Frame {
+        generation,
+        cells: (0..population as i64).map(|x| (x, 0)).collect(),
+    }
+}
+
+#[test]
+fn push_appends_and_seek_moves_back() {
+    let mut timeline = Timeline::new(usize::MAX);
+    timeline.reset(frame(0, 3));
+    timeline.push(frame(1, 2));
+    timeline.push(frame(2, 1));
+
+    assert_eq!(timeline.frame_count(), 3);
+    assert_eq!(timeline.position(), 2);
+    assert_eq!(timeline.seek(0).map(|frame| frame.generation), Some(0));
+    assert_eq!(timeline.position(), 0);
+    assert!(timeline.seek(3).is_none());
+    assert_eq!(timeline.position(), 0);
+}
+
+#[test]
+fn pushing_after_rewinding_drops_the_old_future() {
+    let mut timeline = Timeline::new(usize::MAX);
+    timeline.reset(frame(0, 1));
+    timeline.push(frame(1, 1));
+    timeline.push(frame(2, 1));
+    timeline.seek(0);
+
+    timeline.push(frame(1, 5));
+
+    assert_eq!(timeline.frame_count(), 2);
+    assert_eq!(timeline.position(), 1);
+    assert_eq!(timeline.frame(1), Some(&frame(1, 5)));
+}
+
+#[test]
+fn oldest_frames_are_dropped_over_budget() {
+    let one_frame = {
+        let mut timeline = Timeline::new(usize::MAX);
+        timeline.reset(frame(0, 10));
+        timeline.memory_usage()
+    };
+    let mut timeline = Timeline::new(one_frame * 3);
+    timeline.reset(frame(0, 10));
+    for generation in 1..10 {
+        timeline.push(frame(generation, 10));
+    }
+
+    assert_eq!(timeline.frame_count(), 3);
+    assert_eq!(timeline.memory_usage(), one_frame * 3);
+    assert_eq!(timeline.frame(0).map(|frame| frame.generation), Some(7));
+    assert_eq!(timeline.position(), 2);
+}
+
+#[test]
+fn reset_keeps_at_least_the_current_frame() {
+    let mut timeline = Timeline::new(0);
+    timeline.reset(frame(4, 100));
+    timeline.push(frame(5, 100));
+
+    assert_eq!(timeline.frame_count(), 1);
+    assert_eq!(timeline.frame(0).map(|frame| frame.generation), Some(5));
+}
@@ -0,0 +1,232 @@
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::Print;
+use crossterm::{cursor, execute, queue, terminal};
+
+use crate::domain::{
+    next_generation, Board, Bounds, Cell, Grid, Rule, Soup, Topology, MAX_GRID_SIZE, MAX_UPS,
+    MIN_GRID_SIZE, MIN_UPS,
+};
+
+const DEFAULT_UPS: u32 = 10;
+
+/// Terminal front-end: the same domain as `GameApp`, drawn with half-block
+/// characters so each terminal row shows two grid rows.
+pub struct TuiApp {
+    grid: Grid,
+    rule: Rule,
+    topology: Topology,
+    paused: bool,
+    generation: u64,
+    ups: u32,
+    cursor: (usize, usize),
+    /// Recipe for the last `R` soup; its seed is shown in the status line.
+    soup: Soup,
+    quit: bool,
+}
+
+impl TuiApp {
+    pub fn new(width: usize, height: usize) -> Self {
+        let width = width.clamp(MIN_GRID_SIZE, MAX_GRID_SIZE);
+        let height = height.clamp(MIN_GRID_SIZE, MAX_GRID_SIZE);
+        Self {
+            grid: Grid::new(width, height),
+            rule: Rule::conway(),
+            topology: Topology::Bounded,
+            paused: true,
+            generation: 0,
+            ups: DEFAULT_UPS,
+            cursor: (width / 2, height / 2),
+            soup: Soup::default(),
+            quit: false,
+        }
+    }
+
+    fn step(&mut self) {
+        self.grid = next_generation(&self.grid, &self.rule, self.topology);
+        self.generation += 1;
+    }
+
+    fn toggle_cursor_cell(&mut self) {
+        let (x, y) = self.cursor;
+        let cell = if self.grid.get(x, y).is_alive() {
+            Cell::Dead
+        } else {
+            Cell::Alive
+        };
+        self.grid.set(x, y, cell);
+    }
+
+    fn move_cursor(&mut self, dx: isize, dy: isize) {
+        let (x, y) = self.cursor;
+        self.cursor = (
+            x.saturating_add_signed(dx).min(self.grid.width() - 1),
+            y.saturating_add_signed(dy).min(self.grid.height() - 1),
+        );
+    }
+
+    /// Space, R and C behave as in `GameApp::handle_keyboard`; arrows or
+    /// `hjkl` move the cursor and Enter/`x` toggles the cell under it.
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        if key.kind == KeyEventKind::Release {
+            return;
+        }
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            if key.code == KeyCode::Char('c') {
+                self.quit = true;
+            }
+            return;
+        }
+
+        match key.code {
+            KeyCode::Char(' ') => self.paused = !self.paused,
+            KeyCode::Char('r') | KeyCode::Char('R') => {
+                self.soup.seed = rand::random();
+                let (width, height) = (self.grid.width() as i64, self.grid.height() as i64);
+                self.soup
+                    .fill(&mut self.grid, Bounds::new(0, 0, width, height));
+                self.generation = 0;
+            }
+            KeyCode::Char('c') | KeyCode::Char('C') => {
+                self.grid.clear();
+                self.generation = 0;
+            }
+            KeyCode::Char('n') | KeyCode::Char('N') => self.step(),
+            KeyCode::Char('t') | KeyCode::Char('T') => {
+                self.topology = match self.topology {
+                    Topology::Torus => Topology::Bounded,
+                    _ => Topology::Torus,
+                }
+            }
+            KeyCode::Char('+') | KeyCode::Char('=') => self.ups = (self.ups + 1).min(MAX_UPS),
+            KeyCode::Char('-') => self.ups = self.ups.saturating_sub(1).max(MIN_UPS),
+            KeyCode::Left | KeyCode::Char('h') => self.move_cursor(-1, 0),
+            KeyCode::Right | KeyCode::Char('l') => self.move_cursor(1, 0),
+            KeyCode::Up | KeyCode::Char('k') => self.move_cursor(0, -1),
+            KeyCode::Down | KeyCode::Char('j') => self.move_cursor(0, 1),
+            KeyCode::Enter | KeyCode::Char('x') => self.toggle_cursor_cell(),
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            _ => {}
+        }
+    }
+
+    fn status_line(&self) -> String {
+        let (x, y) = self.cursor;
+        format!(
+            "{} | gen {} | alive {} | {} UPS | {} {} | seed {} | ({x}, {y}) | \
+             Space run R random C clear N step T edges +/- speed Enter toggle q quit",
+            if self.paused { "Paused" } else { "Running" },
+            self.generation,
+            self.grid.population(),
+            self.ups,
+            self.rule,
+            self.topology.name(),
+            self.soup.seed,
+        )
+    }
+
+    fn draw(&self, out: &mut impl Write) -> io::Result<()> {
+        let status_line = self.status_line();
+        queue!(out, cursor::MoveTo(0, 0))?;
+        for line in frame_lines(&self.grid) {
+            queue!(out, Print(line), Print("\r\n"))?;
+        }
+        queue!(
+            out,
+            terminal::Clear(terminal::ClearType::CurrentLine),
+            Print(
+                status_line
+                    .chars()
+                    .take(self.grid.width())
+                    .collect::<String>()
+            ),
+            cursor::MoveTo(self.cursor.0 as u16, (self.cursor.1 / 2) as u16),
+        )?;
+        out.flush()
+    }
+
+    fn event_loop(&mut self, out: &mut impl Write) -> io::Result<()> {
+        let mut last_step = Instant::now();
+        while !self.quit {
+            self.draw(out)?;
+
+            let step_duration = Duration::from_secs_f64(1.0 / self.ups as f64);
+            let timeout = if self.paused {
+                step_duration
+            } else {
+                step_duration.saturating_sub(last_step.elapsed())
+            };
+            if event::poll(timeout)? {
+                if let Event::Key(key) = event::read()? {
+                    self.handle_key(key);
+                }
+            }
+
+            if !self.paused && last_step.elapsed() >= step_duration {
+                self.step();
+                last_step = Instant::now();
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Fills the terminal with a grid and runs until the user quits, restoring
+/// the terminal afterwards even if drawing fails.
+pub fn run() -> io::Result<()> {
+    let (columns, rows) = terminal::size()?;
+    let mut app = TuiApp::new(columns as usize, (rows.saturating_sub(1) as usize) * 2);
+
+    let mut out = io::stdout();
+    let _raw = RawMode::enable()?;
+    execute!(
+        out,
+        terminal::EnterAlternateScreen,
+        terminal::Clear(terminal::ClearType::All)
+    )?;
+    let result = app.event_loop(&mut out);
+    execute!(out, terminal::LeaveAlternateScreen, cursor::Show)?;
+    result
+}
+
+/// Keeps the terminal in raw mode until dropped, so an error on the way out
+/// of `run` still hands back a usable shell.
+struct RawMode;
+
+impl RawMode {
+    fn enable() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        Ok(Self)
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Renders two grid rows per line: `▀` top only, `▄` bottom only, `█` both.
+pub fn frame_lines(grid: &Grid) -> Vec<String> {
+    (0..grid.height())
+        .step_by(2)
+        .map(|y| {
+            (0..grid.width())
+                .map(
+                    |x| match (grid.get(x, y).is_alive(), grid.get(x, y + 1).is_alive()) {
+                        (true, true) => '█',
+                        (true, false) => '▀',
+                        (false, true) => '▄',
+                        (false, false) => ' ',
+                    },
+                )
+                .collect()
+        })
+        .collect()
+}
+
+#[path = "tui_test.rs"]
+#[cfg(test)]
+mod tui_test;
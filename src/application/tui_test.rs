@@ -0,0 +1,74 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use super::{frame_lines, TuiApp};
+use crate::domain::{Board, Cell, Grid, Topology};
+
+fn press(app: &mut TuiApp, code: KeyCode) {
+    app.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+}
+
+#[test]
+fn frame_lines_pack_two_rows_per_line() {
+    let mut grid = Grid::new(4, 3);
+    grid.set(0, 0, Cell::Alive);
+    grid.set(1, 1, Cell::Alive);
+    grid.set(2, 0, Cell::Alive);
+    grid.set(2, 1, Cell::Alive);
+    grid.set(3, 2, Cell::Alive);
+
+    assert_eq!(frame_lines(&grid), vec!["▀▄█ ", "   ▀"]);
+}
+
+#[test]
+fn cursor_editing_toggles_cells_and_stays_on_grid() {
+    let mut app = TuiApp::new(10, 10);
+    assert_eq!(app.cursor, (5, 5));
+
+    press(&mut app, KeyCode::Enter);
+    assert!(app.grid.get(5, 5).is_alive());
+    press(&mut app, KeyCode::Char('x'));
+    assert!(!app.grid.get(5, 5).is_alive());
+
+    for _ in 0..20 {
+        press(&mut app, KeyCode::Left);
+        press(&mut app, KeyCode::Char('j'));
+    }
+    assert_eq!(app.cursor, (0, 9));
+}
+
+#[test]
+fn keys_match_the_gui_shortcuts() {
+    let mut app = TuiApp::new(10, 10);
+    assert!(app.paused);
+    press(&mut app, KeyCode::Char(' '));
+    assert!(!app.paused);
+
+    press(&mut app, KeyCode::Char('r'));
+    assert_eq!(app.generation, 0);
+    press(&mut app, KeyCode::Char('n'));
+    assert_eq!(app.generation, 1);
+
+    press(&mut app, KeyCode::Char('c'));
+    assert_eq!(app.grid.population(), 0);
+    assert_eq!(app.generation, 0);
+
+    press(&mut app, KeyCode::Char('t'));
+    assert_eq!(app.topology, Topology::Torus);
+
+    app.handle_key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL));
+    assert!(app.quit);
+}
+
+#[test]
+fn stepping_uses_the_domain_rules() {
+    let mut app = TuiApp::new(10, 10);
+    for x in 4..7 {
+        app.grid.set(x, 5, Cell::Alive);
+    }
+
+    press(&mut app, KeyCode::Char('n'));
+
+    assert!(app.grid.get(5, 4).is_alive());
+    assert!(app.grid.get(5, 6).is_alive());
+    assert!(!app.grid.get(4, 5).is_alive());
+}
@@ -0,0 +1,101 @@
+use super::cell::Cell;
+
+/// Storage-agnostic view of a Life board addressed by signed coordinates.
+pub trait Board {
+    fn get(&self, x: i64, y: i64) -> Cell;
+
+    /// Writes a cell; writes outside a finite board are ignored.
+    fn set(&mut self, x: i64, y: i64, cell: Cell);
+
+    fn clear(&mut self);
+
+    fn live_cells(&self) -> Box<dyn Iterator<Item = (i64, i64)> + '_>;
+
+    /// Region that can hold live cells, or `None` for an unbounded board.
+    fn extent(&self) -> Option<Bounds>;
+
+    fn population(&self) -> usize {
+        self.live_cells().count()
+    }
+
+    /// Smallest rectangle containing every live cell.
+    fn bounding_box(&self) -> Option<Bounds> {
+        self.live_cells().fold(None, |bounds, (x, y)| match bounds {
+            None => Some(Bounds::new(x, y, 1, 1)),
+            Some(bounds) => Some(bounds.including(x, y)),
+        })
+    }
+}
+
+/// Axis-aligned rectangle of cells with an exclusive right/bottom edge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bounds {
+    pub left: i64,
+    pub top: i64,
+    pub right: i64,
+    pub bottom: i64,
+}
+
+impl Bounds {
+    pub fn new(left: i64, top: i64, width: i64, height: i64) -> Self {
+        Self {
+            left,
+            top,
+            right: left + width,
+            bottom: top + height,
+        }
+    }
+
+    pub fn width(&self) -> i64 {
+        self.right - self.left
+    }
+
+    pub fn height(&self) -> i64 {
+        self.bottom - self.top
+    }
+
+    pub fn contains(&self, x: i64, y: i64) -> bool {
+        x >= self.left && x < self.right && y >= self.top && y < self.bottom
+    }
+
+    pub fn contains_bounds(&self, other: &Bounds) -> bool {
+        other.left >= self.left
+            && other.top >= self.top
+            && other.right <= self.right
+            && other.bottom <= self.bottom
+    }
+
+    /// Cells in both rectangles, if any.
+    pub fn intersection(&self, other: &Bounds) -> Option<Self> {
+        let overlap = Self {
+            left: self.left.max(other.left),
+            top: self.top.max(other.top),
+            right: self.right.min(other.right),
+            bottom: self.bottom.min(other.bottom),
+        };
+        (overlap.width() > 0 && overlap.height() > 0).then_some(overlap)
+    }
+
+    pub fn including(&self, x: i64, y: i64) -> Self {
+        Self {
+            left: self.left.min(x),
+            top: self.top.min(y),
+            right: self.right.max(x + 1),
+            bottom: self.bottom.max(y + 1),
+        }
+    }
+
+    /// A rectangle of at least `width` x `height` cells centred on `self`,
+    /// with `margin` cells of padding on every side.
+    pub fn grown_to(&self, width: i64, height: i64, margin: i64) -> Self {
+        let new_width = width.max(self.width() + 2 * margin);
+        let new_height = height.max(self.height() + 2 * margin);
+        let left = self.left - (new_width - self.width()) / 2;
+        let top = self.top - (new_height - self.height()) / 2;
+        Self::new(left, top, new_width, new_height)
+    }
+}
+
+#[path = "board_test.rs"]
+#[cfg(test)]
+mod board_test;
@@ -0,0 +1,55 @@
+use super::{Board, Bounds};
+use crate::domain::cell::Cell;
+use crate::domain::sparse_grid::SparseGrid;
+
+#[test]
+fn bounds_reports_size_and_containment() {
+    let bounds = Bounds::new(-2, 3, 4, 5);
+    assert_eq!(bounds.width(), 4);
+    assert_eq!(bounds.height(), 5);
+    assert!(bounds.contains(-2, 3));
+    assert!(bounds.contains(1, 7));
+    assert!(!bounds.contains(2, 7));
+    assert!(!bounds.contains(1, 8));
+}
+
+#[test]
+fn bounds_including_grows_to_cover_point() {
+    let bounds = Bounds::new(0, 0, 1, 1).including(-3, 4);
+    assert_eq!(bounds, Bounds::new(-3, 0, 4, 5));
+}
+
+#[test]
+fn bounds_grown_to_keeps_content_centred() {
+    let content = Bounds::new(10, 10, 4, 2);
+    let grown = content.grown_to(20, 20, 3);
+    assert_eq!(grown.width(), 20);
+    assert_eq!(grown.height(), 20);
+    assert!(grown.contains_bounds(&content));
+    assert_eq!(grown.left, 2);
+    assert_eq!(grown.top, 1);
+
+    let padded = content.grown_to(1, 1, 3);
+    assert_eq!(padded, Bounds::new(7, 7, 10, 8));
+}
+
+#[test]
+fn bounding_box_covers_all_live_cells() {
+    let mut plane = SparseGrid::new();
+    assert_eq!(plane.bounding_box(), None);
+
+    plane.set(5, -1, Cell::Alive);
+    plane.set(-2, 3, Cell::Alive);
+    assert_eq!(plane.bounding_box(), Some(Bounds::new(-2, -1, 8, 5)));
+}
+
+#[test]
+fn intersection_is_the_overlap_or_none() {
+    let a = Bounds::new(0, 0, 10, 5);
+
+    assert_eq!(
+        a.intersection(&Bounds::new(8, -2, 10, 4)),
+        Some(Bounds::new(8, 0, 2, 2))
+    );
+    assert_eq!(a.intersection(&Bounds::new(10, 0, 3, 3)), None);
+}
@@ -1,3 +1,5 @@
+use super::rule::Rule;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Cell {
     Alive,
@@ -9,15 +11,19 @@ impl Cell {
         matches!(self, Cell::Alive)
     }
 
-    /// Computes next state based on Game of Life rules:
-    /// - Live cell with 2-3 neighbors survives
-    /// - Dead cell with exactly 3 neighbors becomes alive
+    /// Computes next state based on the given Life-like rule:
+    /// - Live cell survives when the neighbor count is in the rule's S set
+    /// - Dead cell becomes alive when the neighbor count is in the rule's B set
     /// - All other cells die or stay dead
-    pub fn next_state(&self, neighbor_count: u8) -> Cell {
-        match (self, neighbor_count) {
-            (Cell::Alive, 2) | (Cell::Alive, 3) => Cell::Alive,
-            (Cell::Dead, 3) => Cell::Alive,
-            _ => Cell::Dead,
+    pub fn next_state(&self, neighbor_count: u8, rule: &Rule) -> Cell {
+        let alive = match self {
+            Cell::Alive => rule.survives(neighbor_count),
+            Cell::Dead => rule.births(neighbor_count),
+        };
+        if alive {
+            Cell::Alive
+        } else {
+            Cell::Dead
         }
     }
 }
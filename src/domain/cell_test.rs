@@ -1,4 +1,7 @@
 use super::Cell;
+use crate::domain::rule::Rule;
+
+const CONWAY: Rule = Rule::conway();
 
 #[test]
 fn is_alive_reflects_state() {
@@ -12,29 +15,29 @@ fn is_alive_reflects_state() {
 #[test]
 fn next_state_live_cell_underpopulation() {
     let cell = Cell::Alive;
-    assert_eq!(cell.next_state(0), Cell::Dead);
-    assert_eq!(cell.next_state(1), Cell::Dead);
+    assert_eq!(cell.next_state(0, &CONWAY), Cell::Dead);
+    assert_eq!(cell.next_state(1, &CONWAY), Cell::Dead);
 }
 
 #[test]
 fn next_state_live_cell_survives_with_two_or_three_neighbors() {
     let cell = Cell::Alive;
-    assert_eq!(cell.next_state(2), Cell::Alive);
-    assert_eq!(cell.next_state(3), Cell::Alive);
+    assert_eq!(cell.next_state(2, &CONWAY), Cell::Alive);
+    assert_eq!(cell.next_state(3, &CONWAY), Cell::Alive);
 }
 
 #[test]
 fn next_state_live_cell_overpopulation() {
     let cell = Cell::Alive;
     for neighbors in 4..=8 {
-        assert_eq!(cell.next_state(neighbors), Cell::Dead);
+        assert_eq!(cell.next_state(neighbors, &CONWAY), Cell::Dead);
     }
 }
 
 #[test]
 fn next_state_dead_cell_reproduction() {
     let cell = Cell::Dead;
-    assert_eq!(cell.next_state(3), Cell::Alive);
+    assert_eq!(cell.next_state(3, &CONWAY), Cell::Alive);
 }
 
 #[test]
@@ -44,6 +47,14 @@ fn next_state_dead_cell_stays_dead_otherwise() {
         if neighbors == 3 {
             continue;
         }
-        assert_eq!(cell.next_state(neighbors), Cell::Dead);
+        assert_eq!(cell.next_state(neighbors, &CONWAY), Cell::Dead);
     }
 }
+
+#[test]
+fn next_state_follows_custom_rule() {
+    let highlife = Rule::parse("B36/S23").unwrap();
+    assert_eq!(Cell::Dead.next_state(6, &highlife), Cell::Alive);
+    assert_eq!(Cell::Dead.next_state(6, &CONWAY), Cell::Dead);
+    assert_eq!(Cell::Alive.next_state(6, &highlife), Cell::Dead);
+}
@@ -0,0 +1,397 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use super::board::Board;
+use super::cell::Cell;
+use super::rule::Rule;
+use super::rules::next_generation_unbounded;
+use super::spaceship::{canonical_form, find_velocity, Shape};
+use super::sparse_grid::SparseGrid;
+
+/// Longest period tried when identifying an object.
+const MAX_OBJECT_PERIOD: u64 = 60;
+
+type CellTransform = fn((i64, i64)) -> (i64, i64);
+
+/// Keys of `KNOWN_OBJECTS`, mapped to their index in the table.
+type KnownKeys = HashMap<Shape, usize>;
+
+pub struct KnownObject {
+    pub name: &'static str,
+    pub plural: &'static str,
+    /// One phase, in plaintext rows (`O` alive, `.` dead).
+    pub rows: &'static [&'static str],
+}
+
+/// Common Conway's Life objects recognised by `take_census`.
+pub const KNOWN_OBJECTS: [KnownObject; 17] = [
+    KnownObject {
+        name: "block",
+        plural: "blocks",
+        rows: &["OO", "OO"],
+    },
+    KnownObject {
+        name: "beehive",
+        plural: "beehives",
+        rows: &[".OO.", "O..O", ".OO."],
+    },
+    KnownObject {
+        name: "loaf",
+        plural: "loaves",
+        rows: &[".OO.", "O..O", ".O.O", "..O."],
+    },
+    KnownObject {
+        name: "boat",
+        plural: "boats",
+        rows: &["OO.", "O.O", ".O."],
+    },
+    KnownObject {
+        name: "ship",
+        plural: "ships",
+        rows: &["OO.", "O.O", ".OO"],
+    },
+    KnownObject {
+        name: "tub",
+        plural: "tubs",
+        rows: &[".O.", "O.O", ".O."],
+    },
+    KnownObject {
+        name: "pond",
+        plural: "ponds",
+        rows: &[".OO.", "O..O", "O..O", ".OO."],
+    },
+    KnownObject {
+        name: "long boat",
+        plural: "long boats",
+        rows: &["OO..", "O.O.", ".O.O", "..O."],
+    },
+    KnownObject {
+        name: "barge",
+        plural: "barges",
+        rows: &[".O..", "O.O.", ".O.O", "..O."],
+    },
+    KnownObject {
+        name: "blinker",
+        plural: "blinkers",
+        rows: &["OOO"],
+    },
+    KnownObject {
+        name: "toad",
+        plural: "toads",
+        rows: &[".OOO", "OOO."],
+    },
+    KnownObject {
+        name: "beacon",
+        plural: "beacons",
+        rows: &["OO..", "OO..", "..OO", "..OO"],
+    },
+    KnownObject {
+        name: "pentadecathlon",
+        plural: "pentadecathlons",
+        rows: &["..O....O..", "OO.OOOO.OO", "..O....O.."],
+    },
+    KnownObject {
+        name: "glider",
+        plural: "gliders",
+        rows: &[".O.", "..O", "OOO"],
+    },
+    KnownObject {
+        name: "lightweight spaceship",
+        plural: "lightweight spaceships",
+        rows: &[".O..O", "O....", "O...O", "OOOO."],
+    },
+    KnownObject {
+        name: "middleweight spaceship",
+        plural: "middleweight spaceships",
+        rows: &["...O..", ".O...O", "O.....", "O....O", "OOOOO."],
+    },
+    KnownObject {
+        name: "heavyweight spaceship",
+        plural: "heavyweight spaceships",
+        rows: &["...OO..", ".O....O", "O......", "O.....O", "OOOOOO."],
+    },
+];
+
+impl KnownObject {
+    pub fn cells(&self) -> Vec<(i64, i64)> {
+        self.rows
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.chars()
+                    .enumerate()
+                    .filter(|&(_, c)| c == 'O')
+                    .map(move |(x, _)| (x as i64, y as i64))
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CensusEntry {
+    pub name: String,
+    pub plural: String,
+    pub count: usize,
+}
+
+/// Objects found on a board, most common first.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Census {
+    pub entries: Vec<CensusEntry>,
+}
+
+impl Census {
+    pub fn object_count(&self) -> usize {
+        self.entries.iter().map(|entry| entry.count).sum()
+    }
+}
+
+/// Writes the count with the matching noun, e.g. `1 glider` or `12 blocks`.
+impl fmt::Display for CensusEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = if self.count == 1 {
+            &self.name
+        } else {
+            &self.plural
+        };
+        write!(f, "{} {name}", self.count)
+    }
+}
+
+/// Lists the census as `12 blocks, 4 blinkers, 1 glider`.
+impl fmt::Display for Census {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.entries.is_empty() {
+            return write!(f, "no objects");
+        }
+        for (index, entry) in self.entries.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{entry}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Splits live cells into objects: two cells belong to the same object when
+/// they are at most `distance` cells apart horizontally and vertically.
+pub fn separate_objects(
+    cells: impl IntoIterator<Item = (i64, i64)>,
+    distance: i64,
+) -> Vec<Vec<(i64, i64)>> {
+    let mut remaining: HashSet<(i64, i64)> = cells.into_iter().collect();
+    let mut starts: Vec<(i64, i64)> = remaining.iter().copied().collect();
+    starts.sort_unstable_by_key(|&(x, y)| (y, x));
+
+    let mut objects = Vec::new();
+    for start in starts {
+        if !remaining.remove(&start) {
+            continue;
+        }
+        let mut object = vec![start];
+        let mut queue = VecDeque::from([start]);
+        while let Some((x, y)) = queue.pop_front() {
+            for dy in -distance..=distance {
+                for dx in -distance..=distance {
+                    let neighbor = (x + dx, y + dy);
+                    if remaining.remove(&neighbor) {
+                        object.push(neighbor);
+                        queue.push_back(neighbor);
+                    }
+                }
+            }
+        }
+        objects.push(object);
+    }
+    objects
+}
+
+/// Splits an object into its touching pieces when each piece is periodic on
+/// its own and evolving them apart matches evolving them together, e.g. two
+/// blocks a cell apart. Pieces that interact, like the halves of a beacon in
+/// its six-cell phase, stay one object.
+fn split_independent(object: Vec<(i64, i64)>, rule: &Rule) -> Vec<Vec<(i64, i64)>> {
+    let pieces = separate_objects(object.iter().copied(), 1);
+    if pieces.len() < 2 {
+        return vec![object];
+    }
+
+    let mut period = 1;
+    for piece in &pieces {
+        match find_velocity(piece.iter().copied(), rule, MAX_OBJECT_PERIOD) {
+            Some(velocity) => period = lcm(period, velocity.period),
+            None => return vec![object],
+        }
+        if period > MAX_OBJECT_PERIOD {
+            return vec![object];
+        }
+    }
+
+    let plane_of = |cells: &[(i64, i64)]| {
+        let mut plane = SparseGrid::new();
+        for &(x, y) in cells {
+            plane.set(x, y, Cell::Alive);
+        }
+        plane
+    };
+    let mut together = plane_of(&object);
+    let mut apart: Vec<SparseGrid> = pieces.iter().map(|piece| plane_of(piece)).collect();
+    for _ in 0..period {
+        together = next_generation_unbounded(&together, rule);
+        let mut union = HashSet::new();
+        let mut count = 0;
+        for plane in &mut apart {
+            *plane = next_generation_unbounded(plane, rule);
+            count += plane.population();
+            union.extend(plane.live_cells());
+        }
+        let joint: HashSet<(i64, i64)> = together.live_cells().collect();
+        if count != union.len() || union != joint {
+            return vec![object];
+        }
+    }
+    pieces
+}
+
+fn lcm(a: u64, b: u64) -> u64 {
+    let (mut x, mut y) = (a, b);
+    while y != 0 {
+        (x, y) = (y, x % y);
+    }
+    a / x * b
+}
+
+/// Shape of `cells` that is the same for every rotation and reflection.
+pub fn symmetric_canonical_form(cells: &[(i64, i64)]) -> Shape {
+    let transforms: [CellTransform; 8] = [
+        |(x, y)| (x, y),
+        |(x, y)| (-y, x),
+        |(x, y)| (-x, -y),
+        |(x, y)| (y, -x),
+        |(x, y)| (-x, y),
+        |(x, y)| (x, -y),
+        |(x, y)| (y, x),
+        |(x, y)| (-y, -x),
+    ];
+    transforms
+        .into_iter()
+        .map(|transform| canonical_form(cells.iter().map(|&cell| transform(cell))).0)
+        .min()
+        .unwrap_or(Shape { cells: Vec::new() })
+}
+
+/// Separates the board into objects and names each one, using
+/// `KNOWN_OBJECTS` where possible. Nearby pieces that never interact are
+/// counted apart. Oscillators and spaceships are matched in
+/// any phase and orientation.
+pub fn take_census(board: &dyn Board, rule: &Rule, distance: i64) -> Census {
+    let known = known_keys(rule);
+
+    let mut counts: HashMap<(String, String), usize> = HashMap::new();
+    let objects = separate_objects(board.live_cells(), distance)
+        .into_iter()
+        .flat_map(|object| split_independent(object, rule));
+    for object in objects {
+        let (key, description) = object_key(&object, rule);
+        let names = match known.get(&key).map(|&index| &KNOWN_OBJECTS[index]) {
+            Some(known) => (known.name.to_string(), known.plural.to_string()),
+            None => description,
+        };
+        *counts.entry(names).or_default() += 1;
+    }
+
+    let mut entries: Vec<CensusEntry> = counts
+        .into_iter()
+        .map(|((name, plural), count)| CensusEntry {
+            name,
+            plural,
+            count,
+        })
+        .collect();
+    entries.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+    Census { entries }
+}
+
+/// The table's keys under `rule`, computed once per rule since each one runs
+/// the object until it repeats.
+fn known_keys(rule: &Rule) -> Arc<KnownKeys> {
+    static CACHE: OnceLock<Mutex<HashMap<Rule, Arc<KnownKeys>>>> = OnceLock::new();
+    let cache = CACHE.get_or_init(Mutex::default);
+    if let Some(keys) = cache.lock().unwrap().get(rule) {
+        return Arc::clone(keys);
+    }
+
+    let keys: Arc<KnownKeys> = Arc::new(
+        KNOWN_OBJECTS
+            .iter()
+            .enumerate()
+            .map(|(index, object)| (object_key(&object.cells(), rule).0, index))
+            .collect(),
+    );
+    cache
+        .lock()
+        .unwrap()
+        .entry(*rule)
+        .or_insert_with(|| Arc::clone(&keys));
+    keys
+}
+
+/// `size` live cells, e.g. "1 cell" or "8 cells".
+fn cell_count(size: usize) -> String {
+    if size == 1 {
+        "1 cell".to_string()
+    } else {
+        format!("{size} cells")
+    }
+}
+
+/// Smallest symmetric canonical form over all phases of `cells`, plus a
+/// singular/plural description for objects missing from the table.
+fn object_key(cells: &[(i64, i64)], rule: &Rule) -> (Shape, (String, String)) {
+    let size = cell_count(cells.len());
+    let Some(velocity) = find_velocity(cells.iter().copied(), rule, MAX_OBJECT_PERIOD) else {
+        return (
+            symmetric_canonical_form(cells),
+            (
+                format!("unstable object ({size})"),
+                format!("unstable objects ({size})"),
+            ),
+        );
+    };
+
+    let mut plane = SparseGrid::new();
+    for &(x, y) in cells {
+        plane.set(x, y, Cell::Alive);
+    }
+    let mut key = symmetric_canonical_form(cells);
+    for _ in 1..velocity.period {
+        plane = next_generation_unbounded(&plane, rule);
+        let phase: Vec<(i64, i64)> = plane.live_cells().collect();
+        key = key.min(symmetric_canonical_form(&phase));
+    }
+
+    let description = if velocity.is_moving() {
+        (
+            format!("{velocity} spaceship"),
+            format!("{velocity} spaceships"),
+        )
+    } else if velocity.period == 1 {
+        (
+            format!("still life ({size})"),
+            format!("still lifes ({size})"),
+        )
+    } else {
+        let period = velocity.period;
+        (
+            format!("period-{period} oscillator"),
+            format!("period-{period} oscillators"),
+        )
+    };
+    (key, description)
+}
+
+#[path = "census_test.rs"]
+#[cfg(test)]
+mod census_test;
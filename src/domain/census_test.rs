@@ -0,0 +1,147 @@
+use std::sync::Arc;
+
+use super::{known_keys, separate_objects, symmetric_canonical_form, take_census, KNOWN_OBJECTS};
+use crate::domain::board::Board;
+use crate::domain::cell::Cell;
+use crate::domain::rule::Rule;
+use crate::domain::sparse_grid::SparseGrid;
+
+fn known(name: &str) -> Vec<(i64, i64)> {
+    KNOWN_OBJECTS
+        .iter()
+        .find(|object| object.name == name)
+        .unwrap()
+        .cells()
+}
+
+fn place(plane: &mut SparseGrid, cells: &[(i64, i64)], dx: i64, dy: i64) {
+    for &(x, y) in cells {
+        plane.set(x + dx, y + dy, Cell::Alive);
+    }
+}
+
+#[test]
+fn separate_objects_honours_the_distance() {
+    let cells = [(0, 0), (1, 1), (3, 1), (10, 10)];
+
+    let touching = separate_objects(cells, 1);
+    assert_eq!(touching.len(), 3);
+    assert_eq!(touching[0].len(), 2);
+
+    let near = separate_objects(cells, 2);
+    assert_eq!(near.len(), 2);
+    assert_eq!(near[0].len(), 3);
+}
+
+#[test]
+fn canonical_form_matches_every_orientation() {
+    let glider = known("glider");
+    let mirrored: Vec<(i64, i64)> = glider.iter().map(|&(x, y)| (-x, y)).collect();
+    let rotated: Vec<(i64, i64)> = glider.iter().map(|&(x, y)| (y + 5, -x)).collect();
+
+    let shape = symmetric_canonical_form(&glider);
+    assert_eq!(symmetric_canonical_form(&mirrored), shape);
+    assert_eq!(symmetric_canonical_form(&rotated), shape);
+    assert_ne!(symmetric_canonical_form(&known("boat")), shape);
+}
+
+#[test]
+fn census_counts_known_objects_in_any_phase_and_orientation() {
+    let mut plane = SparseGrid::new();
+    place(&mut plane, &known("block"), 0, 0);
+    place(&mut plane, &known("block"), 10, 0);
+    place(&mut plane, &known("beehive"), 20, 0);
+    // Vertical blinker: the other phase of the table entry.
+    place(&mut plane, &[(0, 0), (0, 1), (0, 2)], 0, 10);
+    // Glider reflected and advanced by a generation.
+    place(
+        &mut plane,
+        &[(0, 0), (2, 0), (0, 1), (1, 1), (1, 2)],
+        10,
+        10,
+    );
+
+    let census = take_census(&plane, &Rule::conway(), 1);
+
+    assert_eq!(census.object_count(), 5);
+    assert_eq!(
+        census.to_string(),
+        "2 blocks, 1 beehive, 1 blinker, 1 glider"
+    );
+}
+
+#[test]
+fn census_describes_unknown_objects() {
+    let mut plane = SparseGrid::new();
+    // Snake, a 6-cell still life missing from the table.
+    place(
+        &mut plane,
+        &[(0, 0), (1, 0), (3, 0), (0, 1), (2, 1), (3, 1)],
+        0,
+        0,
+    );
+    // A lone cell dies immediately.
+    place(&mut plane, &[(0, 0)], 20, 20);
+
+    let census = take_census(&plane, &Rule::conway(), 1);
+
+    assert_eq!(
+        census.to_string(),
+        "1 still life (6 cells), 1 unstable object (1 cell)"
+    );
+}
+
+#[test]
+fn every_known_object_is_periodic_and_distinct() {
+    let mut plane = SparseGrid::new();
+    for (index, object) in KNOWN_OBJECTS.iter().enumerate() {
+        place(&mut plane, &object.cells(), index as i64 * 20, 0);
+    }
+
+    // Spaceships have cells two apart, so they need the wider distance.
+    let census = take_census(&plane, &Rule::conway(), 2);
+
+    assert_eq!(census.entries.len(), KNOWN_OBJECTS.len());
+    assert!(census.entries.iter().all(|entry| entry.count == 1));
+    assert_eq!(
+        plane.population(),
+        KNOWN_OBJECTS.iter().map(|o| o.cells().len()).sum()
+    );
+}
+
+#[test]
+fn empty_board_has_no_objects() {
+    let census = take_census(&SparseGrid::new(), &Rule::conway(), 1);
+    assert_eq!(census.object_count(), 0);
+    assert_eq!(census.to_string(), "no objects");
+}
+
+#[test]
+fn known_keys_are_computed_once_per_rule() {
+    let conway = Rule::conway();
+    let day_and_night = Rule::parse("B3678/S34678").unwrap();
+
+    let first = known_keys(&conway);
+
+    assert!(Arc::ptr_eq(&first, &known_keys(&conway)));
+    assert!(!Arc::ptr_eq(&first, &known_keys(&day_and_night)));
+}
+
+#[test]
+fn census_splits_neighbours_that_do_not_interact() {
+    let mut plane = SparseGrid::new();
+    // Two blocks one column apart, within the census gap of each other.
+    place(&mut plane, &known("block"), 0, 0);
+    place(&mut plane, &known("block"), 3, 0);
+    // Beacon in its six-cell phase: the two halves only live together.
+    place(
+        &mut plane,
+        &[(0, 0), (1, 0), (0, 1), (3, 2), (2, 3), (3, 3)],
+        20,
+        0,
+    );
+
+    let census = take_census(&plane, &Rule::conway(), 2);
+
+    assert_eq!(census.to_string(), "2 blocks, 1 beacon");
+}
@@ -0,0 +1,216 @@
+use std::collections::HashSet;
+
+use super::board::{Board, Bounds};
+use super::cell::Cell;
+use super::pattern_file::{PatternFile, PatternFileError};
+use super::rule::Rule;
+use super::transform::Transform;
+
+/// How pasted cells combine with the cells already on the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PasteMode {
+    /// Live pasted cells are added; nothing is erased.
+    #[default]
+    Or,
+    /// Live pasted cells flip the board cell.
+    Xor,
+    /// Only cells alive in both survive.
+    And,
+    /// The pasted rectangle replaces the board, dead cells included.
+    Copy,
+}
+
+pub const PASTE_MODES: [PasteMode; 4] = [
+    PasteMode::Or,
+    PasteMode::Xor,
+    PasteMode::And,
+    PasteMode::Copy,
+];
+
+impl PasteMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PasteMode::Or => "OR",
+            PasteMode::Xor => "XOR",
+            PasteMode::And => "AND",
+            PasteMode::Copy => "Copy",
+        }
+    }
+
+    /// Whether live board cells the clip leaves dead are killed.
+    fn clears_unpasted(&self) -> bool {
+        matches!(self, PasteMode::And | PasteMode::Copy)
+    }
+}
+
+/// Rectangle of cells lifted off a board, with live cells stored relative
+/// to its top-left corner.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Clip {
+    pub width: i64,
+    pub height: i64,
+    pub cells: Vec<(i64, i64)>,
+}
+
+impl Clip {
+    /// Copies every cell of `region`. Only live cells are visited, so huge
+    /// regions of the unbounded plane are cheap.
+    pub fn copy(board: &dyn Board, region: Bounds) -> Self {
+        let mut cells: Vec<(i64, i64)> = board
+            .live_cells()
+            .filter(|&(x, y)| region.contains(x, y))
+            .map(|(x, y)| (x - region.left, y - region.top))
+            .collect();
+        cells.sort_by_key(|&(x, y)| (y, x));
+        Self {
+            width: region.width(),
+            height: region.height(),
+            cells,
+        }
+    }
+
+    /// Copies `region` and then kills every cell in it.
+    pub fn cut(board: &mut dyn Board, region: Bounds) -> Self {
+        let clip = Self::copy(board, region);
+        for &(x, y) in &clip.cells {
+            board.set(region.left + x, region.top + y, Cell::Dead);
+        }
+        clip
+    }
+
+    /// Writes the clip with its top-left corner at `(left, top)`. Cells off a
+    /// bounded board are dropped.
+    pub fn paste(&self, board: &mut dyn Board, left: i64, top: i64, mode: PasteMode) {
+        let alive: HashSet<(i64, i64)> = self.cells.iter().copied().collect();
+        if mode.clears_unpasted() {
+            let target = Bounds::new(left, top, self.width, self.height);
+            let unpasted: Vec<(i64, i64)> = board
+                .live_cells()
+                .filter(|&(x, y)| target.contains(x, y) && !alive.contains(&(x - left, y - top)))
+                .collect();
+            for (x, y) in unpasted {
+                board.set(x, y, Cell::Dead);
+            }
+        }
+
+        for &(dx, dy) in &self.cells {
+            let (x, y) = (left + dx, top + dy);
+            let existing = board.get(x, y).is_alive();
+            let cell = match mode {
+                PasteMode::Or | PasteMode::Copy => Cell::Alive,
+                PasteMode::Xor if existing => Cell::Dead,
+                PasteMode::Xor => Cell::Alive,
+                PasteMode::And if existing => Cell::Alive,
+                PasteMode::And => Cell::Dead,
+            };
+            if cell.is_alive() != existing {
+                board.set(x, y, cell);
+            }
+        }
+    }
+
+    pub fn from_pattern(pattern: &PatternFile) -> Self {
+        let (width, height) = pattern.size();
+        Self {
+            width: width as i64,
+            height: height as i64,
+            cells: pattern
+                .cells
+                .iter()
+                .map(|&(x, y)| (x as i64, y as i64))
+                .collect(),
+        }
+    }
+
+    /// The smallest clip holding the given offsets, which may be negative.
+    pub fn from_offsets(offsets: &[(i32, i32)]) -> Self {
+        let left = offsets.iter().map(|&(x, _)| x).min().unwrap_or(0) as i64;
+        let top = offsets.iter().map(|&(_, y)| y).min().unwrap_or(0) as i64;
+        let right = offsets.iter().map(|&(x, _)| x + 1).max().unwrap_or(0) as i64;
+        let bottom = offsets.iter().map(|&(_, y)| y + 1).max().unwrap_or(0) as i64;
+        Self {
+            width: right - left,
+            height: bottom - top,
+            cells: offsets
+                .iter()
+                .map(|&(x, y)| (x as i64 - left, y as i64 - top))
+                .collect(),
+        }
+    }
+
+    /// The clip rotated or flipped, still anchored at its top-left corner.
+    pub fn transformed(&self, transform: Transform) -> Self {
+        let (far_x, far_y) = transform.apply((self.width - 1, self.height - 1));
+        let (shift_x, shift_y) = (far_x.min(0), far_y.min(0));
+        let mut cells: Vec<(i64, i64)> = self
+            .cells
+            .iter()
+            .map(|&cell| {
+                let (x, y) = transform.apply(cell);
+                (x - shift_x, y - shift_y)
+            })
+            .collect();
+        cells.sort_by_key(|&(x, y)| (y, x));
+        let (width, height) = if transform.swaps_axes() {
+            (self.height, self.width)
+        } else {
+            (self.width, self.height)
+        };
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Rotates or flips the cells of `region` about its centre and returns
+    /// the rectangle they now fill. The turned cells replace everything in
+    /// that rectangle; cells outside both rectangles are untouched. Returns
+    /// `None`, changing nothing, when the rectangle would leave a bounded
+    /// board.
+    pub fn transform_region(
+        board: &mut dyn Board,
+        region: Bounds,
+        transform: Transform,
+    ) -> Option<Bounds> {
+        let destination = if transform.swaps_axes() {
+            let shift = (region.width() - region.height()) / 2;
+            Bounds::new(
+                region.left + shift,
+                region.top - shift,
+                region.height(),
+                region.width(),
+            )
+        } else {
+            region
+        };
+        if let Some(extent) = board.extent() {
+            if !extent.contains_bounds(&destination) {
+                return None;
+            }
+        }
+
+        let clip = Self::cut(board, region).transformed(transform);
+        clip.paste(board, destination.left, destination.top, PasteMode::Copy);
+        Some(destination)
+    }
+
+    /// The live cells as a pattern file, e.g. to write as RLE.
+    pub fn to_pattern(&self, rule: Rule) -> Result<PatternFile, PatternFileError> {
+        let cells = self
+            .cells
+            .iter()
+            .map(|&(x, y)| Ok((i32::try_from(x)?, i32::try_from(y)?)))
+            .collect::<Result<_, std::num::TryFromIntError>>()
+            .map_err(|_| PatternFileError::TooLarge)?;
+        Ok(PatternFile {
+            rule: Some(rule),
+            cells,
+            ..PatternFile::default()
+        })
+    }
+}
+
+#[path = "clip_test.rs"]
+#[cfg(test)]
+mod clip_test;
@@ -0,0 +1,171 @@
+use super::{Clip, PasteMode};
+use crate::domain::board::{Board, Bounds};
+use crate::domain::cell::Cell;
+use crate::domain::grid::Grid;
+use crate::domain::rle;
+use crate::domain::rule::Rule;
+use crate::domain::sparse_grid::SparseGrid;
+use crate::domain::transform::Transform;
+
+fn grid_with(cells: &[(i64, i64)]) -> Grid {
+    let mut grid = Grid::new(8, 8);
+    for &(x, y) in cells {
+        Board::set(&mut grid, x, y, Cell::Alive);
+    }
+    grid
+}
+
+fn sorted(mut cells: Vec<(i64, i64)>) -> Vec<(i64, i64)> {
+    cells.sort_by_key(|&(x, y)| (y, x));
+    cells
+}
+
+fn live(grid: &Grid) -> Vec<(i64, i64)> {
+    grid.live_cells().collect()
+}
+
+#[test]
+fn copy_keeps_cells_relative_to_the_region() {
+    let grid = grid_with(&[(2, 3), (3, 3), (6, 6)]);
+
+    let clip = Clip::copy(&grid, Bounds::new(2, 2, 3, 2));
+
+    assert_eq!(
+        clip,
+        Clip {
+            width: 3,
+            height: 2,
+            cells: vec![(0, 1), (1, 1)],
+        }
+    );
+}
+
+#[test]
+fn cut_clears_the_region() {
+    let mut grid = grid_with(&[(1, 1), (2, 1), (5, 5)]);
+
+    let clip = Clip::cut(&mut grid, Bounds::new(0, 0, 4, 4));
+
+    assert_eq!(clip.cells, vec![(1, 1), (2, 1)]);
+    assert_eq!(live(&grid), vec![(5, 5)]);
+}
+
+#[test]
+fn paste_modes_combine_with_the_board() {
+    let clip = Clip {
+        width: 2,
+        height: 1,
+        cells: vec![(0, 0)],
+    };
+    let existing = [(1, 0), (0, 0)];
+    let cases = [
+        (PasteMode::Or, vec![(0, 0), (1, 0)]),
+        (PasteMode::Xor, vec![(1, 0)]),
+        (PasteMode::And, vec![(0, 0)]),
+        (PasteMode::Copy, vec![(0, 0)]),
+    ];
+    for (mode, expected) in cases {
+        let mut grid = grid_with(&existing);
+        clip.paste(&mut grid, 0, 0, mode);
+        assert_eq!(live(&grid), expected, "{}", mode.name());
+    }
+
+    let mut grid = grid_with(&[(1, 0), (4, 4)]);
+    clip.paste(&mut grid, 0, 0, PasteMode::Or);
+    assert_eq!(live(&grid), vec![(0, 0), (1, 0), (4, 4)]);
+}
+
+#[test]
+fn paste_drops_cells_off_the_grid() {
+    let clip = Clip {
+        width: 2,
+        height: 2,
+        cells: vec![(0, 0), (1, 1)],
+    };
+    let mut grid = Grid::new(8, 8);
+
+    clip.paste(&mut grid, 7, 7, PasteMode::Or);
+
+    assert_eq!(live(&grid), vec![(7, 7)]);
+}
+
+#[test]
+fn rle_round_trip_keeps_the_cells() {
+    let clip = Clip {
+        width: 3,
+        height: 3,
+        cells: vec![(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)],
+    };
+
+    let text = rle::write(&clip.to_pattern(Rule::conway()).unwrap());
+    let parsed = Clip::from_pattern(&rle::parse(&text).unwrap());
+
+    assert_eq!(parsed, clip);
+}
+
+#[test]
+fn from_offsets_shifts_cells_to_the_corner() {
+    let clip = Clip::from_offsets(&[(-1, 2), (1, -1)]);
+
+    assert_eq!(
+        clip,
+        Clip {
+            width: 3,
+            height: 4,
+            cells: vec![(0, 3), (2, 0)],
+        }
+    );
+    assert_eq!(Clip::from_offsets(&[]).width, 0);
+}
+
+#[test]
+fn huge_sparse_regions_only_visit_live_cells() {
+    let mut plane = SparseGrid::new();
+    Board::set(&mut plane, -400_000_000, 7, Cell::Alive);
+    Board::set(&mut plane, 500_000_000, 9, Cell::Alive);
+    let region = Bounds::new(-1_000_000_000, 0, 2_000_000_000, 1_000_000_000);
+
+    let clip = Clip::cut(&mut plane, region);
+    assert_eq!(clip.cells, vec![(600_000_000, 7), (1_500_000_000, 9)]);
+    assert_eq!(plane.population(), 0);
+
+    Board::set(&mut plane, 0, 0, Cell::Alive);
+    clip.paste(&mut plane, -1_000_000_000, 0, PasteMode::Copy);
+    assert_eq!(
+        sorted(plane.live_cells().collect()),
+        vec![(-400_000_000, 7), (500_000_000, 9)]
+    );
+}
+
+#[test]
+fn transform_region_turns_cells_about_the_centre() {
+    let mut grid = grid_with(&[(2, 3), (5, 4), (0, 0), (4, 5)]);
+    let region = Bounds::new(2, 3, 4, 2);
+
+    let turned = Clip::transform_region(&mut grid, region, Transform::RotateClockwise);
+
+    assert_eq!(turned, Some(Bounds::new(3, 2, 2, 4)));
+    assert_eq!(sorted(live(&grid)), vec![(0, 0), (4, 2), (3, 5)]);
+
+    let mut bounds = turned.unwrap();
+    for _ in 0..3 {
+        bounds = Clip::transform_region(&mut grid, bounds, Transform::RotateClockwise).unwrap();
+    }
+    assert_eq!(bounds, region);
+    assert_eq!(sorted(live(&grid)), vec![(0, 0), (2, 3), (5, 4)]);
+}
+
+#[test]
+fn transform_region_refuses_to_leave_the_grid() {
+    let mut grid = grid_with(&[(0, 0), (7, 1)]);
+    let before = live(&grid);
+
+    let turned = Clip::transform_region(
+        &mut grid,
+        Bounds::new(0, 0, 8, 2),
+        Transform::RotateAnticlockwise,
+    );
+
+    assert_eq!(turned, None);
+    assert_eq!(live(&grid), before);
+}
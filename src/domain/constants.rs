@@ -1,4 +1,5 @@
 pub const MIN_GRID_SIZE: usize = 10;
-pub const MAX_GRID_SIZE: usize = 200;
+pub const MAX_GRID_SIZE: usize = 4096;
 pub const MIN_UPS: u32 = 1;
 pub const MAX_UPS: u32 = 60;
+pub const MAX_STEP_EXPONENT: u8 = 32;
@@ -0,0 +1,100 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+
+use super::board::Board;
+use super::spaceship::{canonical_form, Velocity};
+
+/// Periods longer than this many generations are not detected.
+const MAX_DETECTED_PERIOD: usize = 4096;
+
+/// How a board has settled, reported by `CycleDetector`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stability {
+    /// The board stopped changing at generation `since`.
+    Static { since: u64 },
+    /// The board repeats every `period` generations, starting at `since`.
+    Periodic { period: u64, since: u64 },
+    /// The board repeats up to translation: a spaceship travelling with
+    /// `velocity`, first seen in its repeating shape at `since`.
+    Moving { velocity: Velocity, since: u64 },
+}
+
+/// Detects when a board returns to an earlier state, possibly shifted, by
+/// remembering a hash of each generation's shape and where it was.
+#[derive(Default)]
+pub struct CycleDetector {
+    /// Shape hash to the generation it was seen at and its top-left corner.
+    seen: HashMap<u64, (u64, (i64, i64))>,
+    order: VecDeque<u64>,
+    result: Option<Stability>,
+}
+
+impl CycleDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forgets every recorded state, e.g. after the board was edited.
+    pub fn reset(&mut self) {
+        self.seen.clear();
+        self.order.clear();
+        self.result = None;
+    }
+
+    /// Records `board` as generation `generation`. Generations must be
+    /// observed one at a time for the reported period to be exact. Once a
+    /// cycle is found it is kept until `reset`.
+    pub fn observe(&mut self, board: &dyn Board, generation: u64) -> Option<Stability> {
+        if self.result.is_some() {
+            return self.result;
+        }
+
+        let (shape, origin) = canonical_form(board.live_cells());
+        let mut hasher = DefaultHasher::new();
+        shape.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        if let Some(&(first, first_origin)) = self.seen.get(&hash) {
+            let period = generation.saturating_sub(first);
+            if period > 0 {
+                let velocity = Velocity {
+                    period,
+                    dx: origin.0 - first_origin.0,
+                    dy: origin.1 - first_origin.1,
+                };
+                self.result = Some(if velocity.is_moving() {
+                    Stability::Moving {
+                        velocity,
+                        since: first,
+                    }
+                } else if period == 1 {
+                    Stability::Static { since: first }
+                } else {
+                    Stability::Periodic {
+                        period,
+                        since: first,
+                    }
+                });
+                return self.result;
+            }
+        }
+
+        self.seen.insert(hash, (generation, origin));
+        self.order.push_back(hash);
+        if self.order.len() > MAX_DETECTED_PERIOD {
+            if let Some(oldest) = self.order.pop_front() {
+                self.seen.remove(&oldest);
+            }
+        }
+        None
+    }
+
+    pub fn result(&self) -> Option<Stability> {
+        self.result
+    }
+}
+
+#[path = "cycle_test.rs"]
+#[cfg(test)]
+mod cycle_test;
@@ -0,0 +1,124 @@
+use super::{CycleDetector, Stability};
+use crate::domain::board::Board;
+use crate::domain::cell::Cell;
+use crate::domain::grid::Grid;
+use crate::domain::patterns::PATTERNS;
+use crate::domain::rule::Rule;
+use crate::domain::rules::{next_generation, next_generation_unbounded};
+use crate::domain::spaceship::Velocity;
+use crate::domain::sparse_grid::SparseGrid;
+use crate::domain::topology::Topology;
+
+fn grid_with(cells: &[(i32, i32)], size: usize, offset: i32) -> Grid {
+    let mut grid = Grid::new(size, size);
+    for &(x, y) in cells {
+        grid.set((x + offset) as usize, (y + offset) as usize, Cell::Alive);
+    }
+    grid
+}
+
+/// Steps `grid` until the detector reports, giving up after `limit` steps.
+fn run_until_settled(mut grid: Grid, topology: Topology, limit: u64) -> Option<Stability> {
+    let mut detector = CycleDetector::new();
+    for generation in 0..limit {
+        if let Some(stability) = detector.observe(&grid, generation) {
+            return Some(stability);
+        }
+        grid = next_generation(&grid, &Rule::conway(), topology);
+    }
+    None
+}
+
+#[test]
+fn still_life_is_static_from_the_start() {
+    let block = grid_with(&[(0, 0), (1, 0), (0, 1), (1, 1)], 6, 2);
+    assert_eq!(
+        run_until_settled(block, Topology::Bounded, 10),
+        Some(Stability::Static { since: 0 })
+    );
+}
+
+#[test]
+fn blinker_has_period_two() {
+    let blinker = grid_with(PATTERNS[1].cells, 7, 3);
+    assert_eq!(
+        run_until_settled(blinker, Topology::Bounded, 10),
+        Some(Stability::Periodic {
+            period: 2,
+            since: 0
+        })
+    );
+}
+
+#[test]
+fn dying_pattern_reports_when_it_became_empty() {
+    let pair = grid_with(&[(0, 0), (1, 0)], 5, 2);
+    assert_eq!(
+        run_until_settled(pair, Topology::Bounded, 10),
+        Some(Stability::Static { since: 1 })
+    );
+}
+
+#[test]
+fn glider_on_a_torus_is_reported_as_a_spaceship() {
+    let glider = grid_with(PATTERNS[0].cells, 8, 0);
+    assert_eq!(
+        run_until_settled(glider, Topology::Torus, 100),
+        Some(Stability::Moving {
+            velocity: Velocity {
+                period: 4,
+                dx: 1,
+                dy: 1
+            },
+            since: 0
+        })
+    );
+}
+
+#[test]
+fn glider_on_an_unbounded_plane_repeats_up_to_translation() {
+    let mut plane = SparseGrid::new();
+    for &(x, y) in PATTERNS[0].cells {
+        plane.set(x as i64, y as i64, Cell::Alive);
+    }
+    let mut detector = CycleDetector::new();
+    for generation in 0..4 {
+        assert_eq!(detector.observe(&plane, generation), None);
+        plane = next_generation_unbounded(&plane, &Rule::conway());
+    }
+
+    let Some(Stability::Moving { velocity, since: 0 }) = detector.observe(&plane, 4) else {
+        panic!("glider should be detected as moving");
+    };
+    assert_eq!(velocity.to_string(), "c/4 diagonal");
+}
+
+#[test]
+fn partly_moving_board_is_not_a_spaceship() {
+    let mut plane = SparseGrid::new();
+    for &(x, y) in PATTERNS[0].cells {
+        plane.set(x as i64, y as i64, Cell::Alive);
+    }
+    for (x, y) in [(-10, -10), (-9, -10), (-10, -9), (-9, -9)] {
+        plane.set(x, y, Cell::Alive);
+    }
+    let mut detector = CycleDetector::new();
+    for generation in 0..50 {
+        assert_eq!(detector.observe(&plane, generation), None);
+        plane = next_generation_unbounded(&plane, &Rule::conway());
+    }
+}
+
+#[test]
+fn reset_forgets_the_result() {
+    let block = grid_with(&[(0, 0), (1, 0), (0, 1), (1, 1)], 6, 2);
+    let mut detector = CycleDetector::new();
+    detector.observe(&block, 0);
+    detector.observe(&block, 1);
+    assert!(detector.result().is_some());
+
+    detector.reset();
+
+    assert_eq!(detector.result(), None);
+    assert_eq!(detector.observe(&block, 5), None);
+}
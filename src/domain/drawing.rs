@@ -0,0 +1,145 @@
+use std::collections::HashSet;
+
+use super::board::{Board, Bounds};
+
+/// Shape dragged out from one cell to another by a drawing tool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Figure {
+    Line,
+    /// Rectangle with the two cells as opposite corners.
+    Rectangle,
+    /// Ellipse inscribed in that rectangle.
+    Ellipse,
+}
+
+/// Largest region `flood_fill` returns; an empty view of the unbounded plane
+/// would otherwise fill millions of cells.
+pub const MAX_FILL_CELLS: usize = 1 << 22;
+
+impl Figure {
+    /// Cells covered by the figure spanning `from` to `to`; `filled` is ignored
+    /// for lines.
+    pub fn cells(&self, from: (i64, i64), to: (i64, i64), filled: bool) -> Vec<(i64, i64)> {
+        match self {
+            Figure::Line => line(from, to),
+            Figure::Rectangle => rectangle(corners(from, to), filled),
+            Figure::Ellipse => ellipse(corners(from, to), filled),
+        }
+    }
+}
+
+fn corners((x0, y0): (i64, i64), (x1, y1): (i64, i64)) -> Bounds {
+    Bounds::new(x0, y0, 1, 1).including(x1, y1)
+}
+
+/// Bresenham line including both ends; consecutive cells touch, so drags
+/// drawn with it have no gaps.
+pub fn line((x0, y0): (i64, i64), (x1, y1): (i64, i64)) -> Vec<(i64, i64)> {
+    let (dx, dy) = ((x1 - x0).abs(), -(y1 - y0).abs());
+    let (step_x, step_y) = ((x1 - x0).signum(), (y1 - y0).signum());
+    let (mut x, mut y) = (x0, y0);
+    let mut error = dx + dy;
+    let mut cells = Vec::with_capacity((dx - dy + 1) as usize);
+    loop {
+        cells.push((x, y));
+        if (x, y) == (x1, y1) {
+            return cells;
+        }
+        let doubled = 2 * error;
+        if doubled >= dy {
+            error += dy;
+            x += step_x;
+        }
+        if doubled <= dx {
+            error += dx;
+            y += step_y;
+        }
+    }
+}
+
+fn rectangle(bounds: Bounds, filled: bool) -> Vec<(i64, i64)> {
+    let mut cells = Vec::new();
+    for y in bounds.top..bounds.bottom {
+        for x in bounds.left..bounds.right {
+            let edge = x == bounds.left
+                || x == bounds.right - 1
+                || y == bounds.top
+                || y == bounds.bottom - 1;
+            if filled || edge {
+                cells.push((x, y));
+            }
+        }
+    }
+    cells
+}
+
+/// Cells whose centres lie inside the ellipse touching the sides of
+/// `bounds`; the outline keeps those with a neighbour outside it.
+fn ellipse(bounds: Bounds, filled: bool) -> Vec<(i64, i64)> {
+    let radius_x = bounds.width() as f64 / 2.0;
+    let radius_y = bounds.height() as f64 / 2.0;
+    let center_x = bounds.left as f64 + radius_x;
+    let center_y = bounds.top as f64 + radius_y;
+
+    let mut inside = Vec::new();
+    for y in bounds.top..bounds.bottom {
+        let dy = (y as f64 + 0.5 - center_y) / radius_y;
+        let half = radius_x * (1.0 - dy * dy).max(0.0).sqrt();
+        let left = (center_x - half - 0.5).ceil() as i64;
+        let right = (center_x + half - 0.5).floor() as i64;
+        // Keep at least the middle cell so thin ellipses stay connected.
+        let middle = (center_x - 0.5).round() as i64;
+        for x in left.min(middle)..=right.max(middle) {
+            inside.push((x, y));
+        }
+    }
+    if filled {
+        return inside;
+    }
+
+    let set: HashSet<(i64, i64)> = inside.iter().copied().collect();
+    inside
+        .into_iter()
+        .filter(|&(x, y)| {
+            [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)]
+                .iter()
+                .any(|neighbor| !set.contains(neighbor))
+        })
+        .collect()
+}
+
+/// The cells connected to `start` through edge neighbours in the same state,
+/// stopping at the edge of `limit`, or `None` once there are more than
+/// `max_cells` of them.
+pub fn flood_fill(
+    board: &dyn Board,
+    start: (i64, i64),
+    limit: Bounds,
+    max_cells: usize,
+) -> Option<Vec<(i64, i64)>> {
+    if !limit.contains(start.0, start.1) {
+        return Some(Vec::new());
+    }
+    let state = board.get(start.0, start.1);
+    // Only cells reached so far are remembered, so a huge `limit` costs
+    // nothing until the fill actually spreads into it.
+    let mut seen = HashSet::from([start]);
+    let mut pending = vec![start];
+    let mut region = Vec::new();
+    while let Some((x, y)) = pending.pop() {
+        if region.len() == max_cells {
+            return None;
+        }
+        region.push((x, y));
+        for (nx, ny) in [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)] {
+            if limit.contains(nx, ny) && board.get(nx, ny) == state && seen.insert((nx, ny)) {
+                pending.push((nx, ny));
+            }
+        }
+    }
+    Some(region)
+}
+
+#[path = "drawing_test.rs"]
+#[cfg(test)]
+mod drawing_test;
@@ -0,0 +1,95 @@
+use std::collections::HashSet;
+
+use super::{flood_fill, line, Figure};
+use crate::domain::board::{Board, Bounds};
+use crate::domain::cell::Cell;
+use crate::domain::grid::Grid;
+use crate::domain::sparse_grid::SparseGrid;
+
+fn touching(a: (i64, i64), b: (i64, i64)) -> bool {
+    (a.0 - b.0).abs() <= 1 && (a.1 - b.1).abs() <= 1
+}
+
+#[test]
+fn line_runs_between_both_ends_without_gaps() {
+    for to in [(7, 3), (-2, 9), (0, -5), (-6, -6), (0, 0)] {
+        let cells = line((0, 0), to);
+
+        assert_eq!(cells.first(), Some(&(0, 0)));
+        assert_eq!(cells.last(), Some(&to));
+        assert_eq!(cells.len() as i64, to.0.abs().max(to.1.abs()) + 1);
+        assert!(cells.windows(2).all(|pair| touching(pair[0], pair[1])));
+    }
+}
+
+#[test]
+fn rectangle_is_its_border_unless_filled() {
+    let hollow = Figure::Rectangle.cells((4, 3), (1, 1), false);
+    let filled = Figure::Rectangle.cells((1, 1), (4, 3), true);
+
+    assert_eq!(hollow.len(), 10);
+    assert!(!hollow.contains(&(2, 2)));
+    assert_eq!(filled.len(), 12);
+}
+
+#[test]
+fn ellipse_outline_wraps_the_filled_ellipse() {
+    let filled: HashSet<_> = Figure::Ellipse
+        .cells((0, 0), (10, 6), true)
+        .into_iter()
+        .collect();
+    let outline: HashSet<_> = Figure::Ellipse
+        .cells((0, 0), (10, 6), false)
+        .into_iter()
+        .collect();
+
+    assert!(filled.contains(&(5, 3)));
+    assert!(!filled.contains(&(0, 0)));
+    assert!(filled.contains(&(0, 3)) && filled.contains(&(10, 3)));
+    assert!(filled.contains(&(5, 0)) && filled.contains(&(5, 6)));
+    assert!(outline.is_subset(&filled));
+    assert!(!outline.contains(&(5, 3)));
+    for &(x, y) in &filled {
+        assert!(filled.contains(&(10 - x, y)) && filled.contains(&(x, 6 - y)));
+    }
+}
+
+#[test]
+fn thin_ellipses_stay_connected() {
+    let cells = Figure::Ellipse.cells((0, 0), (0, 5), false);
+
+    assert_eq!(cells, (0..6).map(|y| (0, y)).collect::<Vec<_>>());
+}
+
+#[test]
+fn flood_fill_stops_at_other_cells_and_the_limit() {
+    let mut grid = Grid::new(8, 8);
+    for y in 0..8 {
+        Board::set(&mut grid, 3, y, Cell::Alive);
+    }
+
+    let left = flood_fill(&grid, (1, 1), Bounds::new(0, 0, 8, 8), 100).unwrap();
+    let wall = flood_fill(&grid, (3, 5), Bounds::new(0, 0, 8, 8), 100).unwrap();
+    let limited = flood_fill(&grid, (6, 6), Bounds::new(5, 5, 3, 3), 100).unwrap();
+
+    assert_eq!(left.len(), 24);
+    assert!(left.iter().all(|&(x, _)| x < 3));
+    assert_eq!(wall.len(), 8);
+    assert_eq!(limited.len(), 9);
+    assert_eq!(
+        flood_fill(&grid, (9, 9), Bounds::new(0, 0, 8, 8), 100),
+        Some(Vec::new())
+    );
+}
+
+#[test]
+fn flood_fill_gives_up_past_the_cell_limit() {
+    let plane = SparseGrid::new();
+    let huge = Bounds::new(-1 << 40, -1 << 40, 1 << 41, 1 << 41);
+
+    assert_eq!(flood_fill(&plane, (0, 0), huge, 1000), None);
+    assert_eq!(
+        flood_fill(&plane, (0, 0), Bounds::new(0, 0, 10, 100), 1000).map(|cells| cells.len()),
+        Some(1000)
+    );
+}
@@ -1,19 +1,25 @@
-use rand::Rng;
-
+use super::board::{Board, Bounds};
 use super::cell::Cell;
 
-#[derive(Clone)]
+/// Cells held in each storage word.
+pub const WORD_BITS: usize = u64::BITS as usize;
+
+/// Fixed-size board stored as bit-packed rows: cell `x` of a row is bit
+/// `x % 64` of word `x / 64`, and bits past the width are always zero.
+#[derive(Clone, PartialEq, Eq)]
 pub struct Grid {
-    cells: Vec<Vec<Cell>>,
+    words: Vec<u64>,
+    words_per_row: usize,
     width: usize,
     height: usize,
 }
 
 impl Grid {
     pub fn new(width: usize, height: usize) -> Self {
-        let cells = vec![vec![Cell::Dead; width]; height];
+        let words_per_row = width.div_ceil(WORD_BITS);
         Self {
-            cells,
+            words: vec![0; words_per_row * height],
+            words_per_row,
             width,
             height,
         }
@@ -28,8 +34,11 @@ impl Grid {
     }
 
     pub fn get(&self, x: usize, y: usize) -> Cell {
-        if x < self.width && y < self.height {
-            self.cells[y][x]
+        if x < self.width
+            && y < self.height
+            && self.row(y)[x / WORD_BITS] >> (x % WORD_BITS) & 1 == 1
+        {
+            Cell::Alive
         } else {
             Cell::Dead
         }
@@ -37,30 +46,88 @@ impl Grid {
 
     pub fn set(&mut self, x: usize, y: usize, cell: Cell) {
         if x < self.width && y < self.height {
-            self.cells[y][x] = cell;
+            let bit = 1 << (x % WORD_BITS);
+            let word = &mut self.row_mut(y)[x / WORD_BITS];
+            if cell.is_alive() {
+                *word |= bit;
+            } else {
+                *word &= !bit;
+            }
         }
     }
 
-    pub fn randomize(&mut self) {
-        let mut rng = rand::thread_rng();
-        for y in 0..self.height {
-            for x in 0..self.width {
-                self.cells[y][x] = if rng.gen_bool(0.3) {
-                    Cell::Alive
-                } else {
-                    Cell::Dead
-                };
-            }
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    pub fn words_per_row(&self) -> usize {
+        self.words_per_row
+    }
+
+    /// Every row's words back to back. Callers must leave bits past the
+    /// width clear.
+    pub fn words_mut(&mut self) -> &mut [u64] {
+        &mut self.words
+    }
+
+    /// Words of row `y`, least significant bit first.
+    pub fn row(&self, y: usize) -> &[u64] {
+        &self.words[y * self.words_per_row..(y + 1) * self.words_per_row]
+    }
+
+    /// Mutable words of row `y`. Callers must leave bits past the width clear.
+    pub fn row_mut(&mut self, y: usize) -> &mut [u64] {
+        &mut self.words[y * self.words_per_row..(y + 1) * self.words_per_row]
+    }
+}
+
+impl Board for Grid {
+    fn get(&self, x: i64, y: i64) -> Cell {
+        if x < 0 || y < 0 {
+            return Cell::Dead;
         }
+        Grid::get(self, x as usize, y as usize)
     }
 
-    pub fn clear(&mut self) {
-        for y in 0..self.height {
-            for x in 0..self.width {
-                self.cells[y][x] = Cell::Dead;
-            }
+    fn set(&mut self, x: i64, y: i64, cell: Cell) {
+        if x >= 0 && y >= 0 {
+            Grid::set(self, x as usize, y as usize, cell);
         }
     }
+
+    fn clear(&mut self) {
+        Grid::clear(self);
+    }
+
+    fn live_cells(&self) -> Box<dyn Iterator<Item = (i64, i64)> + '_> {
+        Box::new((0..self.height).flat_map(move |y| {
+            self.row(y)
+                .iter()
+                .enumerate()
+                .flat_map(move |(index, &word)| {
+                    let mut remaining = word;
+                    std::iter::from_fn(move || {
+                        (remaining != 0).then(|| {
+                            let bit = remaining.trailing_zeros() as usize;
+                            remaining &= remaining - 1;
+                            index * WORD_BITS + bit
+                        })
+                    })
+                })
+                .map(move |x| (x as i64, y as i64))
+        }))
+    }
+
+    fn extent(&self) -> Option<Bounds> {
+        Some(Bounds::new(0, 0, self.width as i64, self.height as i64))
+    }
+
+    fn population(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
 }
 
 #[path = "grid_test.rs"]
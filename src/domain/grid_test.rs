@@ -1,4 +1,4 @@
-use super::{Cell, Grid};
+use super::{Board, Bounds, Cell, Grid};
 
 #[test]
 fn new_initializes_dead_cells() {
@@ -53,3 +53,34 @@ fn clear_resets_cells_to_dead() {
         }
     }
 }
+
+#[test]
+fn board_trait_uses_signed_coordinates() {
+    let mut grid = Grid::new(3, 3);
+    Board::set(&mut grid, 2, 1, Cell::Alive);
+    Board::set(&mut grid, -1, 0, Cell::Alive);
+    Board::set(&mut grid, 3, 0, Cell::Alive);
+
+    assert_eq!(Board::get(&grid, 2, 1), Cell::Alive);
+    assert_eq!(Board::get(&grid, -1, 0), Cell::Dead);
+    assert_eq!(grid.live_cells().collect::<Vec<_>>(), vec![(2, 1)]);
+    assert_eq!(grid.population(), 1);
+    assert_eq!(grid.extent(), Some(Bounds::new(0, 0, 3, 3)));
+}
+
+#[test]
+fn cells_across_word_boundaries_are_independent() {
+    let mut grid = Grid::new(130, 2);
+    for x in [0, 63, 64, 127, 128, 129] {
+        grid.set(x, 1, Cell::Alive);
+    }
+    grid.set(64, 1, Cell::Dead);
+
+    assert_eq!(grid.get(63, 1), Cell::Alive);
+    assert_eq!(grid.get(64, 1), Cell::Dead);
+    assert_eq!(grid.population(), 5);
+    assert_eq!(
+        grid.live_cells().collect::<Vec<_>>(),
+        vec![(0, 1), (63, 1), (127, 1), (128, 1), (129, 1)]
+    );
+}
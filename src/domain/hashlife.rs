@@ -0,0 +1,532 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use super::board::{Board, Bounds};
+use super::cell::Cell;
+use super::rule::Rule;
+
+/// Index of a canonical quadtree node inside a `HashLife` arena.
+pub type NodeId = u32;
+
+const DEAD_LEAF: NodeId = 0;
+const ALIVE_LEAF: NodeId = 1;
+const MIN_ROOT_LEVEL: u8 = 3;
+/// Roots are kept below this level so that coordinates always fit in an `i64`.
+pub const MAX_ROOT_LEVEL: u8 = 62;
+/// Once the arena grows past this many nodes, unreachable nodes and the
+/// result cache are dropped after the next step.
+const GC_NODE_THRESHOLD: usize = 4_000_000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HashLifeError {
+    /// Rules with birth on zero neighbors would fill the infinite plane.
+    UnsupportedRule(Rule),
+    /// The pattern has spread too far for the root to grow around it.
+    OutOfRange,
+}
+
+impl fmt::Display for HashLifeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HashLifeError::UnsupportedRule(rule) => {
+                write!(
+                    f,
+                    "HashLife cannot run {rule}: B0 rules fill the unbounded plane"
+                )
+            }
+            HashLifeError::OutOfRange => {
+                write!(f, "the pattern has spread too far for HashLife to advance")
+            }
+        }
+    }
+}
+
+impl std::error::Error for HashLifeError {}
+
+#[derive(Clone, Copy)]
+struct Node {
+    level: u8,
+    /// Quadrants in `nw`, `ne`, `sw`, `se` order; unused for leaves.
+    children: [NodeId; 4],
+    population: u64,
+}
+
+/// Memoized quadtree universe on an unbounded plane (Gosper's HashLife).
+///
+/// Identical sub-squares are shared, and the future of each node is cached,
+/// so regular patterns can be advanced by huge powers of two at once.
+/// The root of level `L` covers `[-2^(L-1), 2^(L-1))` on both axes.
+pub struct HashLife {
+    rule: Rule,
+    nodes: Vec<Node>,
+    index: HashMap<[NodeId; 4], NodeId>,
+    results: HashMap<(NodeId, u8), NodeId>,
+    empty: Vec<NodeId>,
+    root: NodeId,
+}
+
+impl HashLife {
+    pub fn new(rule: Rule) -> Result<Self, HashLifeError> {
+        if rule.births(0) {
+            return Err(HashLifeError::UnsupportedRule(rule));
+        }
+
+        let mut universe = Self {
+            rule,
+            nodes: Vec::new(),
+            index: HashMap::new(),
+            results: HashMap::new(),
+            empty: Vec::new(),
+            root: DEAD_LEAF,
+        };
+        universe.reset_arena();
+        universe.root = universe.empty(MIN_ROOT_LEVEL);
+        Ok(universe)
+    }
+
+    /// Builds a universe containing every live cell of `board` at the same
+    /// coordinates.
+    pub fn from_board(board: &dyn Board, rule: Rule) -> Result<Self, HashLifeError> {
+        let mut universe = Self::new(rule)?;
+        for (x, y) in board.live_cells() {
+            universe.set(x, y, Cell::Alive);
+        }
+        Ok(universe)
+    }
+
+    pub fn rule(&self) -> Rule {
+        self.rule
+    }
+
+    pub fn population(&self) -> u64 {
+        self.node_population(self.root)
+    }
+
+    pub fn root(&self) -> NodeId {
+        self.root
+    }
+
+    /// Replaces the whole universe with `node`, centred on the origin.
+    pub fn set_root(&mut self, node: NodeId) {
+        let mut root = node;
+        while self.level(root) < MIN_ROOT_LEVEL {
+            root = self.expand(root);
+        }
+        self.root = root;
+    }
+
+    /// Smallest rectangle containing every live cell.
+    pub fn bounding_box(&self) -> Option<Bounds> {
+        let half = 1i64 << (self.level(self.root) - 1);
+        let bounds = self.extremes(self.root, &mut HashMap::new())?;
+        Some(Bounds::new(
+            bounds.left - half,
+            bounds.top - half,
+            bounds.width(),
+            bounds.height(),
+        ))
+    }
+
+    /// Live cells inside `region`, in no particular order.
+    pub fn live_cells_in(&self, region: Bounds) -> Vec<(i64, i64)> {
+        let half = 1i64 << (self.level(self.root) - 1);
+        let mut cells = Vec::new();
+        self.visit_alive(self.root, -half, -half, region, &mut |x, y| {
+            cells.push((x, y))
+        });
+        cells
+    }
+
+    pub fn set(&mut self, x: i64, y: i64, cell: Cell) {
+        while !self.root_contains(x, y) {
+            if self.level(self.root) >= MAX_ROOT_LEVEL {
+                return;
+            }
+            self.root = self.expand(self.root);
+        }
+
+        let half = 1i64 << (self.level(self.root) - 1);
+        let leaf = Self::leaf(cell);
+        self.root = self.set_in(self.root, (x + half) as u64, (y + half) as u64, leaf);
+    }
+
+    /// Advances the universe by `2^exponent` generations, or leaves it as it
+    /// was when the root would have to grow past `MAX_ROOT_LEVEL`.
+    pub fn step(&mut self, exponent: u8) -> Result<(), HashLifeError> {
+        let exponent = exponent.min(MAX_ROOT_LEVEL - 3);
+
+        // The root must be large enough for the requested jump, and padded so
+        // that nothing can travel out of the centre half during it.
+        while self.level(self.root) < exponent + 2 || !self.is_padded(self.root) {
+            if self.level(self.root) >= MAX_ROOT_LEVEL {
+                return Err(HashLifeError::OutOfRange);
+            }
+            self.root = self.expand(self.root);
+        }
+        let padded = self.expand(self.root);
+
+        self.root = self.advance(padded, exponent);
+
+        if self.nodes.len() > GC_NODE_THRESHOLD {
+            self.collect_garbage();
+        }
+        Ok(())
+    }
+
+    /// Clears `board` and copies the live cells that fit within its extent
+    /// into it.
+    pub fn write_to_board(&self, board: &mut dyn Board) {
+        let half = 1i64 << (self.level(self.root) - 1);
+        self.write_window_to_board(board, Bounds::new(-half, -half, 2 * half, 2 * half));
+    }
+
+    /// Clears `board` and copies only the live cells inside `window` and the
+    /// board's extent, so a huge universe costs no more than what is shown.
+    pub fn write_window_to_board(&self, board: &mut dyn Board, window: Bounds) {
+        board.clear();
+        let window = match board.extent() {
+            Some(extent) => extent.intersection(&window),
+            None => Some(window),
+        };
+        for (x, y) in window.map_or_else(Vec::new, |window| self.live_cells_in(window)) {
+            board.set(x, y, Cell::Alive);
+        }
+    }
+
+    fn visit_alive(
+        &self,
+        node: NodeId,
+        left: i64,
+        top: i64,
+        window: Bounds,
+        visit: &mut impl FnMut(i64, i64),
+    ) {
+        let Node {
+            level,
+            children,
+            population,
+        } = self.nodes[node as usize];
+        let size = 1i64 << level;
+        if population == 0
+            || left >= window.right
+            || top >= window.bottom
+            || left + size <= window.left
+            || top + size <= window.top
+        {
+            return;
+        }
+
+        if level == 0 {
+            visit(left, top);
+            return;
+        }
+
+        let quarter = size / 2;
+        for (quadrant, child) in children.into_iter().enumerate() {
+            let (dx, dy) = quadrant_offset(quadrant);
+            self.visit_alive(
+                child,
+                left + dx * quarter,
+                top + dy * quarter,
+                window,
+                visit,
+            );
+        }
+    }
+
+    /// Live-cell extent of `node` relative to its top-left corner, memoized
+    /// per node so shared sub-squares are only measured once.
+    fn extremes(&self, node: NodeId, memo: &mut HashMap<NodeId, Option<Bounds>>) -> Option<Bounds> {
+        let Node {
+            level,
+            children,
+            population,
+        } = self.nodes[node as usize];
+        if population == 0 {
+            return None;
+        }
+        if level == 0 {
+            return Some(Bounds::new(0, 0, 1, 1));
+        }
+        if let Some(&bounds) = memo.get(&node) {
+            return bounds;
+        }
+
+        let quarter = 1i64 << (level - 1);
+        let mut bounds: Option<Bounds> = None;
+        for (quadrant, child) in children.into_iter().enumerate() {
+            let Some(child_bounds) = self.extremes(child, memo) else {
+                continue;
+            };
+            let (dx, dy) = quadrant_offset(quadrant);
+            let shifted = Bounds::new(
+                child_bounds.left + dx * quarter,
+                child_bounds.top + dy * quarter,
+                child_bounds.width(),
+                child_bounds.height(),
+            );
+            bounds = Some(match bounds {
+                Some(current) => current
+                    .including(shifted.left, shifted.top)
+                    .including(shifted.right - 1, shifted.bottom - 1),
+                None => shifted,
+            });
+        }
+        memo.insert(node, bounds);
+        bounds
+    }
+
+    /// The canonical single-cell node for `cell`.
+    pub fn leaf(cell: Cell) -> NodeId {
+        if cell.is_alive() {
+            ALIVE_LEAF
+        } else {
+            DEAD_LEAF
+        }
+    }
+
+    /// Side length of `node` is `2^level`; single cells are level 0.
+    pub fn level(&self, node: NodeId) -> u8 {
+        self.nodes[node as usize].level
+    }
+
+    /// Quadrants of `node` in `nw`, `ne`, `sw`, `se` order.
+    pub fn children(&self, node: NodeId) -> [NodeId; 4] {
+        self.nodes[node as usize].children
+    }
+
+    pub fn node_population(&self, node: NodeId) -> u64 {
+        self.nodes[node as usize].population
+    }
+
+    fn root_contains(&self, x: i64, y: i64) -> bool {
+        let half = 1i64 << (self.level(self.root) - 1);
+        x >= -half && x < half && y >= -half && y < half
+    }
+
+    fn reset_arena(&mut self) {
+        self.nodes.clear();
+        self.index.clear();
+        self.results.clear();
+        self.empty.clear();
+        self.nodes.push(Node {
+            level: 0,
+            children: [DEAD_LEAF; 4],
+            population: 0,
+        });
+        self.nodes.push(Node {
+            level: 0,
+            children: [DEAD_LEAF; 4],
+            population: 1,
+        });
+        self.empty.push(DEAD_LEAF);
+    }
+
+    /// Returns the canonical node with the given quadrants, which must all
+    /// share one level.
+    pub fn join(&mut self, nw: NodeId, ne: NodeId, sw: NodeId, se: NodeId) -> NodeId {
+        let children = [nw, ne, sw, se];
+        if let Some(&id) = self.index.get(&children) {
+            return id;
+        }
+
+        let population = children
+            .iter()
+            .map(|&child| self.nodes[child as usize].population)
+            .sum();
+        let id = self.nodes.len() as NodeId;
+        self.nodes.push(Node {
+            level: self.level(nw) + 1,
+            children,
+            population,
+        });
+        self.index.insert(children, id);
+        id
+    }
+
+    pub fn empty(&mut self, level: u8) -> NodeId {
+        while self.empty.len() <= level as usize {
+            let below = *self.empty.last().unwrap();
+            let node = self.join(below, below, below, below);
+            self.empty.push(node);
+        }
+        self.empty[level as usize]
+    }
+
+    /// Wraps `node` in a node one level larger, keeping it centred.
+    fn expand(&mut self, node: NodeId) -> NodeId {
+        let [nw, ne, sw, se] = self.children(node);
+        let border = self.empty(self.level(node) - 1);
+        let nw = self.join(border, border, border, nw);
+        let ne = self.join(border, border, ne, border);
+        let sw = self.join(border, sw, border, border);
+        let se = self.join(se, border, border, border);
+        self.join(nw, ne, sw, se)
+    }
+
+    /// True when all live cells sit in the centre half of `node`.
+    fn is_padded(&self, node: NodeId) -> bool {
+        let [nw, ne, sw, se] = self.children(node);
+        let inner = [
+            self.children(nw)[3],
+            self.children(ne)[2],
+            self.children(sw)[1],
+            self.children(se)[0],
+        ];
+        let inner_population: u64 = inner
+            .iter()
+            .map(|&child| self.nodes[child as usize].population)
+            .sum();
+        inner_population == self.nodes[node as usize].population
+    }
+
+    fn set_in(&mut self, node: NodeId, x: u64, y: u64, leaf: NodeId) -> NodeId {
+        let level = self.level(node);
+        if level == 0 {
+            return leaf;
+        }
+
+        let quarter = 1u64 << (level - 1);
+        let quadrant = quadrant_index(x >= quarter, y >= quarter);
+        let mut children = self.children(node);
+        children[quadrant] = self.set_in(children[quadrant], x % quarter, y % quarter, leaf);
+        let [nw, ne, sw, se] = children;
+        self.join(nw, ne, sw, se)
+    }
+
+    /// Returns the centre sub-square of `node` (one level smaller).
+    fn centre(&mut self, node: NodeId) -> NodeId {
+        let [nw, ne, sw, se] = self.children(node);
+        let nw = self.children(nw)[3];
+        let ne = self.children(ne)[2];
+        let sw = self.children(sw)[1];
+        let se = self.children(se)[0];
+        self.join(nw, ne, sw, se)
+    }
+
+    /// Returns the centre of `node` advanced by `2^exponent` generations.
+    /// Requires `exponent <= level - 2`.
+    fn advance(&mut self, node: NodeId, exponent: u8) -> NodeId {
+        let level = self.level(node);
+        if self.nodes[node as usize].population == 0 {
+            return self.empty(level - 1);
+        }
+        if level == 2 {
+            return self.advance_base(node);
+        }
+        if let Some(&result) = self.results.get(&(node, exponent)) {
+            return result;
+        }
+
+        let [nw, ne, sw, se] = self.children(node);
+        let [_, nw_ne, nw_sw, nw_se] = self.children(nw);
+        let [ne_nw, _, ne_sw, ne_se] = self.children(ne);
+        let [sw_nw, sw_ne, _, sw_se] = self.children(sw);
+        let [se_nw, se_ne, se_sw, _] = self.children(se);
+
+        let n01 = self.join(nw_ne, ne_nw, nw_se, ne_sw);
+        let n10 = self.join(nw_sw, nw_se, sw_nw, sw_ne);
+        let n11 = self.join(nw_se, ne_sw, sw_ne, se_nw);
+        let n12 = self.join(ne_sw, ne_se, se_nw, se_ne);
+        let n21 = self.join(sw_ne, se_nw, sw_se, se_sw);
+        let nine = [nw, n01, ne, n10, n11, n12, sw, n21, se];
+
+        // At full speed both halves of the jump advance; otherwise the first
+        // half only re-centres and the whole jump happens in the second half.
+        let full_speed = exponent == level - 2;
+        let mut r = [DEAD_LEAF; 9];
+        for (slot, &sub) in r.iter_mut().zip(nine.iter()) {
+            *slot = if full_speed {
+                self.advance(sub, exponent - 1)
+            } else {
+                self.centre(sub)
+            };
+        }
+
+        let inner_exponent = if full_speed { exponent - 1 } else { exponent };
+        let c00 = self.join(r[0], r[1], r[3], r[4]);
+        let c01 = self.join(r[1], r[2], r[4], r[5]);
+        let c10 = self.join(r[3], r[4], r[6], r[7]);
+        let c11 = self.join(r[4], r[5], r[7], r[8]);
+        let nw = self.advance(c00, inner_exponent);
+        let ne = self.advance(c01, inner_exponent);
+        let sw = self.advance(c10, inner_exponent);
+        let se = self.advance(c11, inner_exponent);
+        let result = self.join(nw, ne, sw, se);
+
+        self.results.insert((node, exponent), result);
+        result
+    }
+
+    /// Advances the centre 2x2 of a 4x4 node by a single generation.
+    fn advance_base(&mut self, node: NodeId) -> NodeId {
+        let mut cells = [[false; 4]; 4];
+        for (quadrant, child) in self.children(node).into_iter().enumerate() {
+            let (qx, qy) = quadrant_offset(quadrant);
+            for (leaf_quadrant, leaf) in self.children(child).into_iter().enumerate() {
+                let (lx, ly) = quadrant_offset(leaf_quadrant);
+                cells[(qy * 2 + ly) as usize][(qx * 2 + lx) as usize] = leaf == ALIVE_LEAF;
+            }
+        }
+
+        let mut next = [DEAD_LEAF; 4];
+        for (quadrant, slot) in next.iter_mut().enumerate() {
+            let (dx, dy) = quadrant_offset(quadrant);
+            let x = 1 + dx as usize;
+            let y = 1 + dy as usize;
+            let neighbors = cells[y - 1..=y + 1]
+                .iter()
+                .flat_map(|row| &row[x - 1..=x + 1])
+                .filter(|&&alive| alive)
+                .count() as u8
+                - cells[y][x] as u8;
+            let cell = if cells[y][x] { Cell::Alive } else { Cell::Dead };
+            if cell.next_state(neighbors, &self.rule).is_alive() {
+                *slot = ALIVE_LEAF;
+            }
+        }
+
+        let [nw, ne, sw, se] = next;
+        self.join(nw, ne, sw, se)
+    }
+
+    /// Rebuilds the arena with only the nodes reachable from the root.
+    fn collect_garbage(&mut self) {
+        let old_nodes = std::mem::take(&mut self.nodes);
+        self.reset_arena();
+        let mut remap = HashMap::new();
+        remap.insert(DEAD_LEAF, DEAD_LEAF);
+        remap.insert(ALIVE_LEAF, ALIVE_LEAF);
+        self.root = self.copy_node(&old_nodes, self.root, &mut remap);
+    }
+
+    fn copy_node(
+        &mut self,
+        old_nodes: &[Node],
+        node: NodeId,
+        remap: &mut HashMap<NodeId, NodeId>,
+    ) -> NodeId {
+        if let Some(&id) = remap.get(&node) {
+            return id;
+        }
+
+        let [nw, ne, sw, se] = old_nodes[node as usize]
+            .children
+            .map(|child| self.copy_node(old_nodes, child, remap));
+        let id = self.join(nw, ne, sw, se);
+        remap.insert(node, id);
+        id
+    }
+}
+
+fn quadrant_index(east: bool, south: bool) -> usize {
+    (south as usize) * 2 + east as usize
+}
+
+fn quadrant_offset(quadrant: usize) -> (i64, i64) {
+    ((quadrant % 2) as i64, (quadrant / 2) as i64)
+}
+
+#[path = "hashlife_test.rs"]
+#[cfg(test)]
+mod hashlife_test;
@@ -0,0 +1,158 @@
+use super::{HashLife, HashLifeError};
+use crate::domain::board::{Board, Bounds};
+use crate::domain::cell::Cell;
+use crate::domain::grid::Grid;
+use crate::domain::patterns::PATTERNS;
+use crate::domain::rule::Rule;
+use crate::domain::rules::next_generation;
+use crate::domain::sparse_grid::SparseGrid;
+use crate::domain::topology::Topology;
+
+fn gosper_gun_grid(size: usize) -> Grid {
+    let mut grid = Grid::new(size, size);
+    let gun = PATTERNS
+        .iter()
+        .find(|pattern| pattern.name == "Gosper Glider Gun")
+        .unwrap();
+    for &(dx, dy) in gun.cells {
+        grid.set(dx as usize + 10, dy as usize + 10, Cell::Alive);
+    }
+    grid
+}
+
+fn plane(universe: &HashLife) -> SparseGrid {
+    let mut plane = SparseGrid::new();
+    universe.write_to_board(&mut plane);
+    plane
+}
+
+#[test]
+fn set_works_far_from_origin() {
+    let mut universe = HashLife::new(Rule::conway()).unwrap();
+    universe.set(-1_000_000, 2_000_000, Cell::Alive);
+    universe.set(3, -4, Cell::Alive);
+
+    let cells = plane(&universe);
+    assert_eq!(cells.get(-1_000_000, 2_000_000), Cell::Alive);
+    assert_eq!(cells.get(3, -4), Cell::Alive);
+    assert_eq!(cells.get(0, 0), Cell::Dead);
+    assert_eq!(universe.population(), 2);
+
+    universe.set(3, -4, Cell::Dead);
+    assert_eq!(universe.population(), 1);
+}
+
+#[test]
+fn rejects_b0_rules() {
+    let rule = Rule::parse("B013/S23").unwrap();
+    assert!(matches!(
+        HashLife::new(rule),
+        Err(HashLifeError::UnsupportedRule(_))
+    ));
+}
+
+#[test]
+fn blinker_oscillates_with_period_two() {
+    let mut universe = HashLife::new(Rule::conway()).unwrap();
+    for x in -1..=1 {
+        universe.set(x, 0, Cell::Alive);
+    }
+
+    universe.step(0).unwrap();
+    let vertical = plane(&universe);
+    for y in -1..=1 {
+        assert_eq!(vertical.get(0, y), Cell::Alive);
+    }
+    assert_eq!(vertical.get(-1, 0), Cell::Dead);
+
+    // Two more generations bring it back to the vertical phase.
+    universe.step(1).unwrap();
+    let vertical_again = plane(&universe);
+    assert_eq!(vertical_again.get(0, -1), Cell::Alive);
+    assert_eq!(vertical_again.get(-1, 0), Cell::Dead);
+    assert_eq!(universe.population(), 3);
+}
+
+#[test]
+fn glider_travels_beyond_initial_bounds() {
+    let mut universe = HashLife::new(Rule::conway()).unwrap();
+    for &(x, y) in &[(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)] {
+        universe.set(x, y, Cell::Alive);
+    }
+
+    universe.step(10).unwrap();
+
+    // 1024 generations move the glider 256 cells diagonally.
+    let moved = plane(&universe);
+    for &(x, y) in &[(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)] {
+        assert_eq!(moved.get(x + 256, y + 256), Cell::Alive);
+    }
+    assert_eq!(universe.population(), 5);
+}
+
+#[test]
+fn matches_naive_engine_on_gosper_gun() {
+    let grid = gosper_gun_grid(120);
+    let mut expected = grid.clone();
+    for _ in 0..64 {
+        expected = next_generation(&expected, &Rule::conway(), Topology::Bounded);
+    }
+
+    let mut jumped = HashLife::from_board(&grid, Rule::conway()).unwrap();
+    jumped.step(6).unwrap();
+    let mut stepped = HashLife::from_board(&grid, Rule::conway()).unwrap();
+    for _ in 0..64 {
+        stepped.step(0).unwrap();
+    }
+
+    for universe in [jumped, stepped] {
+        let mut actual = Grid::new(120, 120);
+        universe.write_to_board(&mut actual);
+        for y in 0..120 {
+            for x in 0..120 {
+                assert_eq!(actual.get(x, y), expected.get(x, y), "cell ({x}, {y})");
+            }
+        }
+    }
+}
+
+#[test]
+fn runs_gosper_gun_for_a_million_generations() {
+    let grid = gosper_gun_grid(60);
+    let mut universe = HashLife::from_board(&grid, Rule::conway()).unwrap();
+
+    universe.step(20).unwrap();
+
+    // One five-cell glider every 30 generations on top of the 36-cell gun.
+    let gliders = (1u64 << 20) / 30;
+    let population = universe.population();
+    assert!(population >= 36 + 5 * (gliders - 2), "{population}");
+    assert!(population <= 60 + 5 * (gliders + 2), "{population}");
+}
+
+#[test]
+fn step_refuses_to_grow_the_root_past_the_coordinate_range() {
+    let mut universe = HashLife::new(Rule::conway()).unwrap();
+    let corner = -(1i64 << 61);
+    for (x, y) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+        universe.set(corner + x, corner + y, Cell::Alive);
+    }
+
+    assert_eq!(universe.step(0), Err(HashLifeError::OutOfRange));
+
+    assert_eq!(universe.population(), 4);
+    assert_eq!(plane(&universe).get(corner, corner), Cell::Alive);
+}
+
+#[test]
+fn write_window_to_board_copies_only_the_window() {
+    let mut universe = HashLife::new(Rule::conway()).unwrap();
+    universe.set(-500, 3, Cell::Alive);
+    universe.set(2, 3, Cell::Alive);
+    universe.set(9000, 3, Cell::Alive);
+
+    let mut plane = SparseGrid::new();
+    universe.write_window_to_board(&mut plane, Bounds::new(0, 0, 10, 10));
+
+    assert_eq!(plane.live_cells().collect::<Vec<_>>(), vec![(2, 3)]);
+}
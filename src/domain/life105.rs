@@ -0,0 +1,98 @@
+use super::life106;
+use super::pattern_file::{normalize_cells, PatternFile, PatternFileError};
+use super::plaintext;
+use super::rule::Rule;
+
+pub const HEADER: &str = "#Life 1.05";
+
+/// Parses a Life 1.05 pattern: `#D` description lines, an optional `#N`
+/// (Conway) or `#R s/b` rule line and `#P x y` blocks of `.`/`*` rows.
+pub fn parse(text: &str) -> Result<PatternFile, PatternFileError> {
+    let mut lines = text.lines().enumerate();
+    match lines.next() {
+        Some((_, line)) if line.trim() == HEADER => {}
+        _ => return Err(PatternFileError::MissingHeader),
+    }
+
+    let mut pattern = PatternFile::default();
+    let mut cells = Vec::new();
+    let mut block: Option<(i64, i64)> = None;
+    for (index, line) in lines {
+        let line = line.trim();
+        if let Some(directive) = line.strip_prefix("#P") {
+            let invalid = || PatternFileError::InvalidCoordinates { line: index + 1 };
+            let mut fields = directive.split_whitespace();
+            let (Some(x), Some(y), None) = (fields.next(), fields.next(), fields.next()) else {
+                return Err(invalid());
+            };
+            let x = x.parse::<i64>().map_err(|_| invalid())?;
+            let y = y.parse::<i64>().map_err(|_| invalid())?;
+            block = Some((x, y));
+            continue;
+        }
+        if let Some(rulestring) = line.strip_prefix("#R") {
+            pattern.rule = Some(Rule::parse(rulestring)?);
+            continue;
+        }
+        if line == "#N" {
+            pattern.rule = Some(Rule::conway());
+            continue;
+        }
+        if let Some(comment) = line.strip_prefix('#') {
+            life106::read_comment(&mut pattern, comment);
+            continue;
+        }
+        if line.is_empty() {
+            continue;
+        }
+
+        let (left, top) = block.get_or_insert((0, 0));
+        for (x, character) in line.chars().enumerate() {
+            match character {
+                '.' => {}
+                '*' | 'O' => cells.push((*left + x as i64, *top)),
+                c => {
+                    return Err(PatternFileError::UnexpectedCharacter {
+                        line: index + 1,
+                        character: c,
+                    })
+                }
+            }
+        }
+        *top += 1;
+    }
+
+    pattern.cells = normalize_cells(cells)?;
+    Ok(pattern)
+}
+
+pub fn write(pattern: &PatternFile) -> String {
+    let mut output = format!("{HEADER}\n");
+    if let Some(name) = &pattern.name {
+        output.push_str(&format!("#D Name: {name}\n"));
+    }
+    if let Some(author) = &pattern.author {
+        output.push_str(&format!("#D Author: {author}\n"));
+    }
+    for comment in &pattern.comments {
+        output.push_str(&format!("#D {comment}\n"));
+    }
+    match pattern.rule {
+        Some(rule) if rule != Rule::conway() => {
+            output.push_str(&format!("#R {}\n", rule.to_survival_birth()));
+        }
+        _ => output.push_str("#N\n"),
+    }
+
+    output.push_str("#P 0 0\n");
+    let rows = plaintext::write(&PatternFile {
+        cells: pattern.cells.clone(),
+        ..PatternFile::default()
+    });
+    output.push_str(&rows.replace('O', "*"));
+    output
+}
+
+#[path = "life105_test.rs"]
+#[cfg(test)]
+mod life105_test;
@@ -0,0 +1,54 @@
+use super::{parse, write};
+use crate::domain::pattern_file::{PatternFile, PatternFileError};
+use crate::domain::rule::Rule;
+
+#[test]
+fn parse_combines_blocks() {
+    let text = "#Life 1.05\n#D Two blinkers\n#N\n#P -5 -1\n***\n#P 2 3\n*\n*\n*\n";
+    let pattern = parse(text).unwrap();
+
+    assert_eq!(pattern.comments, vec!["Two blinkers"]);
+    assert_eq!(pattern.rule, Some(Rule::conway()));
+    assert_eq!(
+        pattern.cells,
+        vec![(0, 0), (1, 0), (2, 0), (7, 4), (7, 5), (7, 6)]
+    );
+}
+
+#[test]
+fn parse_reads_survival_birth_rule() {
+    let pattern = parse("#Life 1.05\n#R 23/36\n#P 0 0\n.*\n").unwrap();
+    assert_eq!(pattern.rule, Rule::parse("B36/S23").ok());
+    assert_eq!(pattern.cells, vec![(0, 0)]);
+}
+
+#[test]
+fn parse_rejects_bad_input() {
+    assert_eq!(parse("#Life 1.06\n"), Err(PatternFileError::MissingHeader));
+    assert_eq!(
+        parse("#Life 1.05\n#P 0\n*\n"),
+        Err(PatternFileError::InvalidCoordinates { line: 2 })
+    );
+    assert_eq!(
+        parse("#Life 1.05\n#P 0 0\n*o\n"),
+        Err(PatternFileError::UnexpectedCharacter {
+            line: 3,
+            character: 'o'
+        })
+    );
+}
+
+#[test]
+fn write_round_trips() {
+    let pattern = PatternFile {
+        name: Some("Glider".to_string()),
+        author: None,
+        comments: vec!["c/4 diagonal".to_string()],
+        rule: Rule::parse("B36/S23").ok(),
+        cells: vec![(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)],
+    };
+
+    let text = write(&pattern);
+    assert!(text.contains("#R 23/36\n#P 0 0\n.*\n..*\n***\n"));
+    assert_eq!(parse(&text).unwrap(), pattern);
+}
@@ -0,0 +1,66 @@
+use super::pattern_file::{normalize_cells, PatternFile, PatternFileError};
+
+pub const HEADER: &str = "#Life 1.06";
+
+/// Parses a Life 1.06 pattern: the header followed by one `x y` coordinate
+/// pair per live cell.
+pub fn parse(text: &str) -> Result<PatternFile, PatternFileError> {
+    let mut lines = text.lines().enumerate();
+    match lines.next() {
+        Some((_, line)) if line.trim() == HEADER => {}
+        _ => return Err(PatternFileError::MissingHeader),
+    }
+
+    let mut pattern = PatternFile::default();
+    let mut cells = Vec::new();
+    for (index, line) in lines {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(comment) = line.strip_prefix('#') {
+            read_comment(&mut pattern, comment);
+            continue;
+        }
+
+        let invalid = || PatternFileError::InvalidCoordinates { line: index + 1 };
+        let mut fields = line.split_whitespace();
+        let (Some(x), Some(y), None) = (fields.next(), fields.next(), fields.next()) else {
+            return Err(invalid());
+        };
+        let x = x.parse::<i64>().map_err(|_| invalid())?;
+        let y = y.parse::<i64>().map_err(|_| invalid())?;
+        cells.push((x, y));
+    }
+
+    pattern.cells = normalize_cells(cells)?;
+    Ok(pattern)
+}
+
+/// Writes the live cells only; the format has no place for a name or rule.
+pub fn write(pattern: &PatternFile) -> String {
+    let mut output = format!("{HEADER}\n");
+    for &(x, y) in &pattern.cells {
+        output.push_str(&format!("{x} {y}\n"));
+    }
+    output
+}
+
+/// Reads the `#D` description lines Life 1.0x tools emit, picking out
+/// `Name:` and `Author:` entries.
+pub fn read_comment(pattern: &mut PatternFile, comment: &str) {
+    if let Some(text) = comment.strip_prefix('D') {
+        let text = text.trim();
+        if let Some(name) = text.strip_prefix("Name:") {
+            pattern.name = Some(name.trim().to_string());
+        } else if let Some(author) = text.strip_prefix("Author:") {
+            pattern.author = Some(author.trim().to_string());
+        } else {
+            pattern.comments.push(text.to_string());
+        }
+    }
+}
+
+#[path = "life106_test.rs"]
+#[cfg(test)]
+mod life106_test;
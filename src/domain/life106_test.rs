@@ -0,0 +1,45 @@
+use super::{parse, write};
+use crate::domain::pattern_file::{PatternFile, PatternFileError};
+
+#[test]
+fn parse_normalizes_signed_coordinates() {
+    let text = "#Life 1.06\n0 -1\n1 0\n-1 1\n0 1\n1 1\n";
+    let pattern = parse(text).unwrap();
+
+    assert_eq!(pattern.cells, vec![(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]);
+}
+
+#[test]
+fn parse_reads_description_lines() {
+    let text = "#Life 1.06\n#D Name: Blinker\n#D period 2\n0 0\n1 0\n2 0\n";
+    let pattern = parse(text).unwrap();
+
+    assert_eq!(pattern.name.as_deref(), Some("Blinker"));
+    assert_eq!(pattern.comments, vec!["period 2"]);
+    assert_eq!(pattern.cells.len(), 3);
+}
+
+#[test]
+fn parse_rejects_bad_input() {
+    assert_eq!(parse("0 0\n"), Err(PatternFileError::MissingHeader));
+    assert_eq!(
+        parse("#Life 1.06\n0 0\n1\n"),
+        Err(PatternFileError::InvalidCoordinates { line: 3 })
+    );
+    assert_eq!(
+        parse("#Life 1.06\n0 x\n"),
+        Err(PatternFileError::InvalidCoordinates { line: 2 })
+    );
+}
+
+#[test]
+fn write_round_trips_cells() {
+    let pattern = PatternFile {
+        cells: vec![(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)],
+        ..PatternFile::default()
+    };
+
+    let text = write(&pattern);
+    assert_eq!(text, "#Life 1.06\n1 0\n2 1\n0 2\n1 2\n2 2\n");
+    assert_eq!(parse(&text).unwrap(), pattern);
+}
@@ -0,0 +1,304 @@
+use std::collections::HashMap;
+
+use super::cell::Cell;
+use super::grid::Grid;
+use super::hashlife::{HashLife, NodeId, MAX_ROOT_LEVEL};
+use super::pattern_file::{normalize_cells, PatternFile, PatternFileError};
+use super::rule::Rule;
+
+pub const HEADER: &str = "[M2]";
+/// Macrocell leaves are 8x8 bitmaps.
+const LEAF_LEVEL: u8 = 3;
+const LEAF_SIZE: i64 = 1 << LEAF_LEVEL;
+
+/// Pattern loaded from a macrocell file: the quadtree goes straight into a
+/// HashLife universe, so it never has to be expanded cell by cell.
+pub struct Macrocell {
+    pub universe: HashLife,
+    /// Name, comments and rule; `cells` is left empty.
+    pub info: PatternFile,
+}
+
+/// Parses a Golly macrocell (`.mc`) file.
+pub fn parse(text: &str) -> Result<Macrocell, PatternFileError> {
+    let mut lines = text.lines().enumerate();
+    match lines.next() {
+        Some((_, line)) if line.trim_start().starts_with(HEADER) => {}
+        _ => return Err(PatternFileError::MissingHeader),
+    }
+
+    let mut info = PatternFile::default();
+    let mut body = Vec::new();
+    for (index, line) in lines {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(comment) = line.strip_prefix('#') {
+            let mut chars = comment.chars();
+            let kind = chars.next();
+            let text = chars.as_str().trim();
+            match kind {
+                Some('R') => info.rule = Some(Rule::parse(text.split(':').next().unwrap_or(text))?),
+                Some('N') => info.name = Some(text.to_string()),
+                Some('O') => info.author = Some(text.to_string()),
+                Some('C') => info.comments.push(text.to_string()),
+                _ => {}
+            }
+            continue;
+        }
+        body.push((index + 1, line));
+    }
+
+    let rule = info.rule.unwrap_or_default();
+    let mut universe = HashLife::new(rule).map_err(|_| PatternFileError::UnsupportedRule(rule))?;
+
+    // Node lines are numbered from 1; 0 stands for an empty node.
+    let mut nodes: Vec<NodeId> = Vec::with_capacity(body.len());
+    for (line_number, line) in body {
+        let invalid = || PatternFileError::InvalidNode { line: line_number };
+        let starts_with_digit = line.starts_with(|c: char| c.is_ascii_digit());
+        let node = if starts_with_digit {
+            let fields = line
+                .split_whitespace()
+                .map(|field| field.parse::<usize>().map_err(|_| invalid()))
+                .collect::<Result<Vec<_>, _>>()?;
+            let [level, quadrants @ ..] = fields.as_slice() else {
+                return Err(invalid());
+            };
+            // Stepping pads the root by a level, which must stay addressable.
+            if quadrants.len() != 4
+                || *level <= LEAF_LEVEL as usize
+                || *level >= MAX_ROOT_LEVEL as usize - 1
+            {
+                return Err(invalid());
+            }
+
+            let level = *level as u8;
+            let mut children = [0; 4];
+            for (child, &reference) in children.iter_mut().zip(quadrants) {
+                *child = match reference {
+                    0 => universe.empty(level - 1),
+                    n => *nodes.get(n - 1).ok_or_else(invalid)?,
+                };
+                if universe.level(*child) != level - 1 {
+                    return Err(invalid());
+                }
+            }
+            let [nw, ne, sw, se] = children;
+            universe.join(nw, ne, sw, se)
+        } else {
+            parse_leaf(&mut universe, line).ok_or_else(invalid)?
+        };
+        nodes.push(node);
+    }
+
+    if let Some(&root) = nodes.last() {
+        universe.set_root(root);
+    }
+    Ok(Macrocell { universe, info })
+}
+
+/// Writes the universe as a macrocell file, sharing repeated sub-squares.
+pub fn write(universe: &HashLife, info: &PatternFile) -> String {
+    let mut output = format!("{HEADER} (game_of_life)\n");
+    output.push_str(&format!("#R {}\n", universe.rule()));
+    if let Some(name) = &info.name {
+        output.push_str(&format!("#N {name}\n"));
+    }
+    if let Some(author) = &info.author {
+        output.push_str(&format!("#O {author}\n"));
+    }
+    for comment in &info.comments {
+        output.push_str(&format!("#C {comment}\n"));
+    }
+
+    let mut numbers = HashMap::new();
+    let mut lines = Vec::new();
+    write_node(universe, universe.root(), &mut numbers, &mut lines);
+    if lines.is_empty() {
+        lines.push("$".to_string());
+    }
+
+    for line in lines {
+        output.push_str(&line);
+        output.push('\n');
+    }
+    output
+}
+
+/// Builds a universe holding `pattern`'s cells with its top-left at the
+/// origin.
+pub fn universe_from_pattern(pattern: &PatternFile) -> Result<HashLife, PatternFileError> {
+    let rule = pattern.rule.unwrap_or_default();
+    let mut universe = HashLife::new(rule).map_err(|_| PatternFileError::UnsupportedRule(rule))?;
+    for &(x, y) in &pattern.cells {
+        universe.set(x as i64, y as i64, Cell::Alive);
+    }
+    Ok(universe)
+}
+
+/// Converts a loaded macrocell into an explicit cell list, refusing patterns
+/// with more than `max_population` cells.
+pub fn to_pattern_file(
+    macrocell: &Macrocell,
+    max_population: u64,
+) -> Result<PatternFile, PatternFileError> {
+    if macrocell.universe.population() > max_population {
+        return Err(PatternFileError::TooLarge);
+    }
+
+    let mut pattern = macrocell.info.clone();
+    pattern.rule = Some(macrocell.universe.rule());
+    if let Some(bounds) = macrocell.universe.bounding_box() {
+        pattern.cells = normalize_cells(macrocell.universe.live_cells_in(bounds))?;
+    }
+    Ok(pattern)
+}
+
+/// Replaces `grid` with the universe centred in it, or explains why the
+/// bounded grid cannot hold it and leaves the grid untouched.
+pub fn load_into_grid(universe: &HashLife, grid: &mut Grid) -> Result<(), PatternFileError> {
+    let Some(bounds) = universe.bounding_box() else {
+        grid.clear();
+        return Ok(());
+    };
+
+    let grid_width = grid.width() as i64;
+    let grid_height = grid.height() as i64;
+    if bounds.width() > grid_width || bounds.height() > grid_height {
+        return Err(PatternFileError::TooLargeForGrid {
+            width: bounds.width() as u64,
+            height: bounds.height() as u64,
+            grid_width: grid.width(),
+            grid_height: grid.height(),
+        });
+    }
+
+    grid.clear();
+    let left = (grid_width - bounds.width()) / 2;
+    let top = (grid_height - bounds.height()) / 2;
+    for (x, y) in universe.live_cells_in(bounds) {
+        grid.set(
+            (x - bounds.left + left) as usize,
+            (y - bounds.top + top) as usize,
+            Cell::Alive,
+        );
+    }
+    Ok(())
+}
+
+fn parse_leaf(universe: &mut HashLife, line: &str) -> Option<NodeId> {
+    let mut alive = [[false; LEAF_SIZE as usize]; LEAF_SIZE as usize];
+    let (mut x, mut y) = (0usize, 0usize);
+    for character in line.chars() {
+        match character {
+            '.' => x += 1,
+            '*' => {
+                *alive.get_mut(y)?.get_mut(x)? = true;
+                x += 1;
+            }
+            '$' => {
+                x = 0;
+                y += 1;
+            }
+            _ => return None,
+        }
+    }
+
+    Some(build_square(universe, &alive, 0, 0, LEAF_LEVEL))
+}
+
+fn build_square(
+    universe: &mut HashLife,
+    alive: &[[bool; LEAF_SIZE as usize]; LEAF_SIZE as usize],
+    left: usize,
+    top: usize,
+    level: u8,
+) -> NodeId {
+    if level == 0 {
+        let cell = if alive[top][left] {
+            Cell::Alive
+        } else {
+            Cell::Dead
+        };
+        return HashLife::leaf(cell);
+    }
+
+    let half = 1usize << (level - 1);
+    let nw = build_square(universe, alive, left, top, level - 1);
+    let ne = build_square(universe, alive, left + half, top, level - 1);
+    let sw = build_square(universe, alive, left, top + half, level - 1);
+    let se = build_square(universe, alive, left + half, top + half, level - 1);
+    universe.join(nw, ne, sw, se)
+}
+
+/// Emits `node` after its children and returns its line number (0 if empty).
+fn write_node(
+    universe: &HashLife,
+    node: NodeId,
+    numbers: &mut HashMap<NodeId, usize>,
+    lines: &mut Vec<String>,
+) -> usize {
+    if universe.node_population(node) == 0 {
+        return 0;
+    }
+    if let Some(&number) = numbers.get(&node) {
+        return number;
+    }
+
+    let level = universe.level(node);
+    let line = if level == LEAF_LEVEL {
+        leaf_line(universe, node)
+    } else {
+        let [nw, ne, sw, se] = universe
+            .children(node)
+            .map(|child| write_node(universe, child, numbers, lines));
+        format!("{level} {nw} {ne} {sw} {se}")
+    };
+
+    lines.push(line);
+    numbers.insert(node, lines.len());
+    lines.len()
+}
+
+fn leaf_line(universe: &HashLife, node: NodeId) -> String {
+    let mut alive = [[false; LEAF_SIZE as usize]; LEAF_SIZE as usize];
+    collect_leaf(universe, node, 0, 0, &mut alive);
+
+    let mut line = String::new();
+    for row in alive {
+        let text: String = row
+            .iter()
+            .map(|&cell| if cell { '*' } else { '.' })
+            .collect();
+        line.push_str(text.trim_end_matches('.'));
+        line.push('$');
+    }
+    line
+}
+
+fn collect_leaf(
+    universe: &HashLife,
+    node: NodeId,
+    left: usize,
+    top: usize,
+    alive: &mut [[bool; LEAF_SIZE as usize]; LEAF_SIZE as usize],
+) {
+    let level = universe.level(node);
+    if level == 0 {
+        alive[top][left] = node == HashLife::leaf(Cell::Alive);
+        return;
+    }
+
+    let half = 1usize << (level - 1);
+    let [nw, ne, sw, se] = universe.children(node);
+    collect_leaf(universe, nw, left, top, alive);
+    collect_leaf(universe, ne, left + half, top, alive);
+    collect_leaf(universe, sw, left, top + half, alive);
+    collect_leaf(universe, se, left + half, top + half, alive);
+}
+
+#[path = "macrocell_test.rs"]
+#[cfg(test)]
+mod macrocell_test;
@@ -0,0 +1,191 @@
+use super::{load_into_grid, parse, to_pattern_file, universe_from_pattern, write};
+use crate::domain::board::Bounds;
+use crate::domain::cell::Cell;
+use crate::domain::grid::Grid;
+use crate::domain::pattern_file::{PatternFile, PatternFileError};
+use crate::domain::rule::Rule;
+
+const GLIDER_LEAF: &str = "[M2] (golly 4.2)\n#R B3/S23\n#N Glider\n#C c/4 diagonal\n.*$..*$***$\n";
+
+fn sorted_cells(cells: Vec<(i64, i64)>) -> Vec<(i64, i64)> {
+    let mut cells = cells;
+    cells.sort_by_key(|&(x, y)| (y, x));
+    cells
+}
+
+#[test]
+fn parse_reads_single_leaf_centred_on_origin() {
+    let macrocell = parse(GLIDER_LEAF).unwrap();
+
+    assert_eq!(macrocell.info.name.as_deref(), Some("Glider"));
+    assert_eq!(macrocell.info.comments, vec!["c/4 diagonal"]);
+    assert_eq!(macrocell.info.rule, Some(Rule::conway()));
+    assert_eq!(macrocell.universe.population(), 5);
+    assert_eq!(
+        macrocell.universe.bounding_box(),
+        Some(Bounds::new(-4, -4, 3, 3))
+    );
+}
+
+#[test]
+fn parse_shares_referenced_nodes() {
+    let text = "[M2]\n.*$..*$***$\n4 1 0 0 1\n";
+    let macrocell = parse(text).unwrap();
+
+    assert_eq!(macrocell.universe.population(), 10);
+    let cells = macrocell
+        .universe
+        .live_cells_in(Bounds::new(-8, -8, 16, 16));
+    assert!(cells.contains(&(-7, -8)));
+    assert!(cells.contains(&(1, 0)));
+}
+
+#[test]
+fn parse_handles_astronomically_large_patterns() {
+    let mut text = String::from("[M2]\n");
+    text.push_str(&"********$".repeat(8));
+    text.push('\n');
+    for level in 4..=30 {
+        let child = level - 3;
+        text.push_str(&format!("{level} {child} {child} {child} {child}\n"));
+    }
+
+    let macrocell = parse(&text).unwrap();
+    assert_eq!(macrocell.universe.population(), 1u64 << 60);
+    assert_eq!(
+        to_pattern_file(&macrocell, 1_000_000).err(),
+        Some(PatternFileError::TooLarge)
+    );
+
+    let mut grid = Grid::new(200, 200);
+    assert_eq!(
+        load_into_grid(&macrocell.universe, &mut grid),
+        Err(PatternFileError::TooLargeForGrid {
+            width: 1 << 30,
+            height: 1 << 30,
+            grid_width: 200,
+            grid_height: 200,
+        })
+    );
+}
+
+#[test]
+fn failed_load_into_grid_leaves_the_grid_untouched() {
+    let macrocell = parse(GLIDER_LEAF).unwrap();
+    let mut grid = Grid::new(2, 2);
+    grid.set(1, 1, Cell::Alive);
+    let before = grid.clone();
+
+    assert!(matches!(
+        load_into_grid(&macrocell.universe, &mut grid),
+        Err(PatternFileError::TooLargeForGrid { .. })
+    ));
+    assert!(grid == before);
+}
+
+#[test]
+fn parse_rejects_malformed_input() {
+    assert_eq!(
+        parse("#R B3/S23\n.*$\n").err(),
+        Some(PatternFileError::MissingHeader)
+    );
+    assert_eq!(
+        parse("[M2]\n.*$\n4 1 0 0 2\n").err(),
+        Some(PatternFileError::InvalidNode { line: 3 })
+    );
+    assert_eq!(
+        parse("[M2]\n.*$\n4 1 0 0 0\n5 1 0 0 0\n").err(),
+        Some(PatternFileError::InvalidNode { line: 4 })
+    );
+    assert_eq!(
+        parse("[M2]\n.x$\n").err(),
+        Some(PatternFileError::InvalidNode { line: 2 })
+    );
+    assert_eq!(
+        parse("[M2]\n#R B0/S8\n.*$\n").err(),
+        Some(PatternFileError::UnsupportedRule(
+            Rule::parse("B0/S8").unwrap()
+        ))
+    );
+}
+
+#[test]
+fn write_round_trips_and_shares_identical_squares() {
+    let pattern = PatternFile {
+        name: Some("Two blocks".to_string()),
+        rule: Rule::parse("B36/S23").ok(),
+        cells: vec![
+            (0, 0),
+            (1, 0),
+            (0, 1),
+            (1, 1),
+            (64, 64),
+            (65, 64),
+            (64, 65),
+            (65, 65),
+        ],
+        ..PatternFile::default()
+    };
+    let universe = universe_from_pattern(&pattern).unwrap();
+
+    let text = write(&universe, &pattern);
+    assert!(text.starts_with("[M2]"));
+    assert!(text.contains("#R B36/S23\n#N Two blocks\n"));
+    // Both blocks sit at the same offset inside identical 8x8 leaves.
+    assert_eq!(
+        text.lines().filter(|line| line.starts_with("**$")).count(),
+        1
+    );
+
+    let parsed = parse(&text).unwrap();
+    assert_eq!(parsed.info.name.as_deref(), Some("Two blocks"));
+    assert_eq!(parsed.universe.rule(), Rule::parse("B36/S23").unwrap());
+    let everything = Bounds::new(-1024, -1024, 2048, 2048);
+    assert_eq!(
+        sorted_cells(parsed.universe.live_cells_in(everything)),
+        sorted_cells(universe.live_cells_in(everything))
+    );
+}
+
+#[test]
+fn load_into_grid_centres_small_patterns() {
+    let macrocell = parse(GLIDER_LEAF).unwrap();
+    let mut grid = Grid::new(7, 7);
+    grid.set(0, 0, Cell::Alive);
+
+    load_into_grid(&macrocell.universe, &mut grid).unwrap();
+
+    assert_eq!(grid.get(0, 0), Cell::Dead);
+    assert_eq!(grid.get(3, 2), Cell::Alive);
+    assert_eq!(grid.get(4, 3), Cell::Alive);
+    for x in 2..=4 {
+        assert_eq!(grid.get(x, 4), Cell::Alive);
+    }
+}
+
+#[test]
+fn to_pattern_file_lists_cells_from_top_left() {
+    let macrocell = parse(GLIDER_LEAF).unwrap();
+    let pattern = to_pattern_file(&macrocell, 100).unwrap();
+
+    assert_eq!(pattern.name.as_deref(), Some("Glider"));
+    assert_eq!(pattern.cells, vec![(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]);
+}
+
+#[test]
+fn parse_rejects_roots_too_large_to_step() {
+    // A leaf in the north-west corner of ever larger empty nodes.
+    let mut text = "[M2]\n.*$\n".to_string();
+    for level in 4..=60 {
+        text.push_str(&format!("{level} {} 0 0 0\n", level - 3));
+    }
+
+    let mut macrocell = parse(&text).unwrap();
+    assert!(macrocell.universe.step(0).is_ok());
+
+    text.push_str("61 58 0 0 0\n");
+    assert_eq!(
+        parse(&text).err(),
+        Some(PatternFileError::InvalidNode { line: 60 })
+    );
+}
@@ -1,11 +1,44 @@
+pub mod board;
 pub mod cell;
+pub mod census;
+pub mod clip;
 pub mod constants;
+pub mod cycle;
+pub mod drawing;
 pub mod grid;
+pub mod hashlife;
+pub mod life105;
+pub mod life106;
+pub mod macrocell;
+pub mod pattern_file;
+pub mod pattern_format;
 pub mod patterns;
+pub mod plaintext;
+pub mod rle;
+pub mod rule;
 pub mod rules;
+pub mod soup;
+pub mod spaceship;
+pub mod sparse_grid;
+pub mod topology;
+pub mod transform;
 
+pub use board::{Board, Bounds};
 pub use cell::Cell;
-pub use constants::{MAX_GRID_SIZE, MAX_UPS, MIN_GRID_SIZE, MIN_UPS};
+pub use census::{take_census, Census};
+pub use clip::{Clip, PasteMode, PASTE_MODES};
+pub use constants::{MAX_GRID_SIZE, MAX_STEP_EXPONENT, MAX_UPS, MIN_GRID_SIZE, MIN_UPS};
+pub use cycle::{CycleDetector, Stability};
+pub use drawing::{flood_fill, line, Figure, MAX_FILL_CELLS};
 pub use grid::Grid;
+pub use hashlife::HashLife;
+pub use pattern_file::{PatternFile, MAX_CELL_LIST_POPULATION};
+pub use pattern_format::{parse_any, PatternFormat, PATTERN_FORMATS};
 pub use patterns::PATTERNS;
-pub use rules::next_generation;
+pub use rule::{Rule, RULE_PRESETS};
+pub use rules::{next_generation, next_generation_unbounded, Stepper, STEPPERS};
+pub use soup::{Soup, SYMMETRIES};
+pub use spaceship::find_velocity;
+pub use sparse_grid::SparseGrid;
+pub use topology::{Topology, TOPOLOGIES};
+pub use transform::{Transform, TRANSFORMS};
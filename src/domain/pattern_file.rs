@@ -0,0 +1,136 @@
+use std::fmt;
+
+use super::board::Board;
+use super::rule::{Rule, RuleParseError};
+
+/// Pattern read from or written to a pattern file. `cells` uses the same
+/// `(x, y)` offsets as `PatternDefinition::cells`, relative to the top-left
+/// corner of the pattern's bounding box.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PatternFile {
+    pub name: Option<String>,
+    pub author: Option<String>,
+    pub comments: Vec<String>,
+    pub rule: Option<Rule>,
+    pub cells: Vec<(i32, i32)>,
+}
+
+/// Most live cells a pattern may list explicitly; bigger macrocell files are
+/// only loaded through `macrocell::parse`, never expanded into a cell list.
+pub const MAX_CELL_LIST_POPULATION: usize = 5_000_000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatternFileError {
+    MissingHeader,
+    InvalidHeader(String),
+    InvalidRule(RuleParseError),
+    UnexpectedCharacter {
+        line: usize,
+        character: char,
+    },
+    InvalidCoordinates {
+        line: usize,
+    },
+    InvalidNode {
+        line: usize,
+    },
+    UnsupportedRule(Rule),
+    TooLargeForGrid {
+        width: u64,
+        height: u64,
+        grid_width: usize,
+        grid_height: usize,
+    },
+    /// Pattern does not fit in `i32` offsets, or lists more than
+    /// `MAX_CELL_LIST_POPULATION` cells.
+    TooLarge,
+}
+
+impl fmt::Display for PatternFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatternFileError::MissingHeader => write!(f, "missing or unrecognised header"),
+            PatternFileError::InvalidHeader(header) => write!(f, "invalid header '{header}'"),
+            PatternFileError::InvalidRule(err) => write!(f, "invalid rule: {err}"),
+            PatternFileError::UnexpectedCharacter { line, character } => {
+                write!(f, "unexpected character '{character}' on line {line}")
+            }
+            PatternFileError::InvalidCoordinates { line } => {
+                write!(f, "expected 'x y' coordinates on line {line}")
+            }
+            PatternFileError::InvalidNode { line } => {
+                write!(f, "invalid macrocell node on line {line}")
+            }
+            PatternFileError::UnsupportedRule(rule) => {
+                write!(
+                    f,
+                    "rule {rule} cannot be stored as a quadtree (B0 rules are unsupported)"
+                )
+            }
+            PatternFileError::TooLargeForGrid {
+                width,
+                height,
+                grid_width,
+                grid_height,
+            } => write!(
+                f,
+                "pattern is {width} x {height} cells but the grid is only \
+                 {grid_width} x {grid_height}; switch to the unbounded plane to load it"
+            ),
+            PatternFileError::TooLarge => write!(f, "pattern is too large"),
+        }
+    }
+}
+
+impl std::error::Error for PatternFileError {}
+
+impl From<RuleParseError> for PatternFileError {
+    fn from(err: RuleParseError) -> Self {
+        PatternFileError::InvalidRule(err)
+    }
+}
+
+impl PatternFile {
+    /// Captures every live cell of `board`, shifted so the bounding box starts
+    /// at `(0, 0)`.
+    pub fn from_board(board: &dyn Board, rule: Rule) -> Result<Self, PatternFileError> {
+        Ok(Self {
+            rule: Some(rule),
+            cells: normalize_cells(board.live_cells())?,
+            ..Self::default()
+        })
+    }
+
+    /// Width and height of the bounding box of `cells`, measured from `(0, 0)`.
+    pub fn size(&self) -> (i32, i32) {
+        self.cells.iter().fold((0, 0), |(width, height), &(x, y)| {
+            (width.max(x + 1), height.max(y + 1))
+        })
+    }
+}
+
+/// Shifts absolute coordinates so their bounding box starts at `(0, 0)` and
+/// sorts them in row-major order.
+pub fn normalize_cells(
+    cells: impl IntoIterator<Item = (i64, i64)>,
+) -> Result<Vec<(i32, i32)>, PatternFileError> {
+    let cells: Vec<(i64, i64)> = cells.into_iter().collect();
+    let min_x = cells.iter().map(|&(x, _)| x).min().unwrap_or(0);
+    let min_y = cells.iter().map(|&(_, y)| y).min().unwrap_or(0);
+
+    let mut normalized = cells
+        .into_iter()
+        .map(|(x, y)| {
+            let dx = i32::try_from(x - min_x).map_err(|_| PatternFileError::TooLarge)?;
+            let dy = i32::try_from(y - min_y).map_err(|_| PatternFileError::TooLarge)?;
+            Ok((dx, dy))
+        })
+        .collect::<Result<Vec<_>, PatternFileError>>()?;
+    normalized.sort_by_key(|&(x, y)| (y, x));
+    normalized.dedup();
+    Ok(normalized)
+}
+
+#[path = "pattern_file_test.rs"]
+#[cfg(test)]
+mod pattern_file_test;
@@ -0,0 +1,39 @@
+use super::{normalize_cells, PatternFile, PatternFileError};
+use crate::domain::board::Board;
+use crate::domain::cell::Cell;
+use crate::domain::rule::Rule;
+use crate::domain::sparse_grid::SparseGrid;
+
+#[test]
+fn from_board_normalizes_to_bounding_box() {
+    let mut plane = SparseGrid::new();
+    plane.set(-4, 10, Cell::Alive);
+    plane.set(-2, 11, Cell::Alive);
+    plane.set(-3, 12, Cell::Alive);
+
+    let pattern = PatternFile::from_board(&plane, Rule::conway()).unwrap();
+    assert_eq!(pattern.cells, vec![(0, 0), (2, 1), (1, 2)]);
+    assert_eq!(pattern.size(), (3, 3));
+    assert_eq!(pattern.rule, Some(Rule::conway()));
+}
+
+#[test]
+fn from_board_of_empty_board_has_no_cells() {
+    let pattern = PatternFile::from_board(&SparseGrid::new(), Rule::conway()).unwrap();
+    assert!(pattern.cells.is_empty());
+    assert_eq!(pattern.size(), (0, 0));
+}
+
+#[test]
+fn normalize_cells_shifts_sorts_and_dedups() {
+    let cells = normalize_cells(vec![(5, -1), (3, -2), (5, -1), (4, 0)]).unwrap();
+    assert_eq!(cells, vec![(0, 0), (2, 1), (1, 2)]);
+}
+
+#[test]
+fn normalize_cells_rejects_huge_spans() {
+    assert_eq!(
+        normalize_cells(vec![(0, 0), (i64::from(i32::MAX) + 1, 0)]),
+        Err(PatternFileError::TooLarge)
+    );
+}
@@ -0,0 +1,120 @@
+use super::life105;
+use super::life106;
+use super::macrocell;
+use super::pattern_file::{PatternFile, PatternFileError, MAX_CELL_LIST_POPULATION};
+use super::plaintext;
+use super::rle;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatternFormat {
+    Rle,
+    Plaintext,
+    Life105,
+    Life106,
+    Macrocell,
+}
+
+pub const PATTERN_FORMATS: [PatternFormat; 5] = [
+    PatternFormat::Rle,
+    PatternFormat::Plaintext,
+    PatternFormat::Life105,
+    PatternFormat::Life106,
+    PatternFormat::Macrocell,
+];
+
+impl PatternFormat {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PatternFormat::Rle => "RLE",
+            PatternFormat::Plaintext => "Plaintext",
+            PatternFormat::Life105 => "Life 1.05",
+            PatternFormat::Life106 => "Life 1.06",
+            PatternFormat::Macrocell => "Macrocell",
+        }
+    }
+
+    pub fn extensions(&self) -> &'static [&'static str] {
+        match self {
+            PatternFormat::Rle => &["rle"],
+            PatternFormat::Plaintext => &["cells"],
+            PatternFormat::Life105 | PatternFormat::Life106 => &["lif", "life"],
+            PatternFormat::Macrocell => &["mc"],
+        }
+    }
+
+    /// Picks the writer for a file name, defaulting to RLE. `.lif` files are
+    /// written as Life 1.06.
+    pub fn from_extension(extension: &str) -> Self {
+        match extension.to_ascii_lowercase().as_str() {
+            "cells" => PatternFormat::Plaintext,
+            "lif" | "life" => PatternFormat::Life106,
+            "mc" => PatternFormat::Macrocell,
+            _ => PatternFormat::Rle,
+        }
+    }
+
+    /// Whether saving to one of its extensions writes this format; Life 1.05
+    /// shares `.lif` with Life 1.06 and so cannot be chosen when saving.
+    pub fn is_saved_by_extension(&self) -> bool {
+        Self::from_extension(self.extensions()[0]) == *self
+    }
+
+    /// Guesses the format from the file's header rather than its extension.
+    pub fn detect(text: &str) -> Self {
+        let first_line = text
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .unwrap_or("");
+
+        if first_line.starts_with(macrocell::HEADER) {
+            PatternFormat::Macrocell
+        } else if first_line == life106::HEADER {
+            PatternFormat::Life106
+        } else if first_line == life105::HEADER {
+            PatternFormat::Life105
+        } else if first_line.starts_with('!') || is_plaintext_row(first_line) {
+            PatternFormat::Plaintext
+        } else {
+            PatternFormat::Rle
+        }
+    }
+
+    pub fn parse(&self, text: &str) -> Result<PatternFile, PatternFileError> {
+        match self {
+            PatternFormat::Rle => rle::parse(text),
+            PatternFormat::Plaintext => plaintext::parse(text),
+            PatternFormat::Life105 => life105::parse(text),
+            PatternFormat::Life106 => life106::parse(text),
+            PatternFormat::Macrocell => macrocell::to_pattern_file(
+                &macrocell::parse(text)?,
+                MAX_CELL_LIST_POPULATION as u64,
+            ),
+        }
+    }
+
+    pub fn write(&self, pattern: &PatternFile) -> Result<String, PatternFileError> {
+        Ok(match self {
+            PatternFormat::Rle => rle::write(pattern),
+            PatternFormat::Plaintext => plaintext::write(pattern),
+            PatternFormat::Life105 => life105::write(pattern),
+            PatternFormat::Life106 => life106::write(pattern),
+            PatternFormat::Macrocell => {
+                macrocell::write(&macrocell::universe_from_pattern(pattern)?, pattern)
+            }
+        })
+    }
+}
+
+/// Parses a pattern in whichever supported format its header indicates.
+pub fn parse_any(text: &str) -> Result<PatternFile, PatternFileError> {
+    PatternFormat::detect(text).parse(text)
+}
+
+fn is_plaintext_row(line: &str) -> bool {
+    line.chars().all(|c| matches!(c, '.' | 'O' | '*'))
+}
+
+#[path = "pattern_format_test.rs"]
+#[cfg(test)]
+mod pattern_format_test;
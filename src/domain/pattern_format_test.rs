@@ -0,0 +1,73 @@
+use super::{parse_any, PatternFormat, PATTERN_FORMATS};
+use crate::domain::pattern_file::PatternFile;
+use crate::domain::rule::Rule;
+
+const GLIDER: [(i32, i32); 5] = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+
+#[test]
+fn detect_uses_headers() {
+    assert_eq!(
+        PatternFormat::detect("#Life 1.06\n0 0\n"),
+        PatternFormat::Life106
+    );
+    assert_eq!(
+        PatternFormat::detect("\n#Life 1.05\n#P 0 0\n*\n"),
+        PatternFormat::Life105
+    );
+    assert_eq!(
+        PatternFormat::detect("!Name: Glider\n.O\n"),
+        PatternFormat::Plaintext
+    );
+    assert_eq!(
+        PatternFormat::detect(".O.\nOOO\n"),
+        PatternFormat::Plaintext
+    );
+    assert_eq!(
+        PatternFormat::detect("#N Glider\nx = 3, y = 3\nbo$2bo$3o!"),
+        PatternFormat::Rle
+    );
+    assert_eq!(
+        PatternFormat::detect("x = 1, y = 1\no!"),
+        PatternFormat::Rle
+    );
+}
+
+#[test]
+fn from_extension_defaults_to_rle() {
+    assert_eq!(
+        PatternFormat::from_extension("CELLS"),
+        PatternFormat::Plaintext
+    );
+    assert_eq!(PatternFormat::from_extension("lif"), PatternFormat::Life106);
+    assert_eq!(PatternFormat::from_extension("rle"), PatternFormat::Rle);
+    assert_eq!(
+        PatternFormat::from_extension("mc"),
+        PatternFormat::Macrocell
+    );
+    assert_eq!(PatternFormat::from_extension("txt"), PatternFormat::Rle);
+}
+
+#[test]
+fn only_formats_reached_by_extension_are_offered_for_saving() {
+    let saved: Vec<_> = PATTERN_FORMATS
+        .iter()
+        .filter(|format| format.is_saved_by_extension())
+        .collect();
+
+    assert!(!saved.contains(&&PatternFormat::Life105));
+    assert_eq!(saved.len(), PATTERN_FORMATS.len() - 1);
+}
+
+#[test]
+fn every_format_round_trips_through_detection() {
+    let pattern = PatternFile {
+        cells: GLIDER.to_vec(),
+        rule: Some(Rule::conway()),
+        ..PatternFile::default()
+    };
+
+    for format in PATTERN_FORMATS {
+        let parsed = parse_any(&format.write(&pattern).unwrap()).unwrap();
+        assert_eq!(parsed.cells, GLIDER.to_vec(), "{}", format.name());
+    }
+}
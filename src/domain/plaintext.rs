@@ -0,0 +1,71 @@
+use std::collections::HashSet;
+
+use super::pattern_file::{PatternFile, PatternFileError};
+
+/// Parses a plaintext (`.cells`) pattern: `!` comment lines followed by rows
+/// of `.` (dead) and `O` or `*` (alive).
+pub fn parse(text: &str) -> Result<PatternFile, PatternFileError> {
+    let mut pattern = PatternFile::default();
+    let mut y = 0;
+
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim_end();
+        if let Some(comment) = line.strip_prefix('!') {
+            if let Some(name) = comment.strip_prefix("Name:") {
+                pattern.name = Some(name.trim().to_string());
+            } else if let Some(author) = comment.strip_prefix("Author:") {
+                pattern.author = Some(author.trim().to_string());
+            } else {
+                pattern.comments.push(comment.trim().to_string());
+            }
+            continue;
+        }
+
+        for (x, character) in line.chars().enumerate() {
+            match character {
+                '.' => {}
+                'O' | '*' => pattern.cells.push((x as i32, y)),
+                c => {
+                    return Err(PatternFileError::UnexpectedCharacter {
+                        line: index + 1,
+                        character: c,
+                    })
+                }
+            }
+        }
+        y += 1;
+    }
+
+    Ok(pattern)
+}
+
+pub fn write(pattern: &PatternFile) -> String {
+    let mut output = String::new();
+    if let Some(name) = &pattern.name {
+        output.push_str(&format!("!Name: {name}\n"));
+    }
+    if let Some(author) = &pattern.author {
+        output.push_str(&format!("!Author: {author}\n"));
+    }
+    for comment in &pattern.comments {
+        output.push_str(&format!("!{comment}\n"));
+    }
+
+    let (width, height) = pattern.size();
+    let alive: HashSet<(i32, i32)> = pattern.cells.iter().copied().collect();
+    for y in 0..height {
+        let row: String = (0..width)
+            .map(|x| if alive.contains(&(x, y)) { 'O' } else { '.' })
+            .collect();
+        // Keep at least one character so blank rows survive editors that
+        // strip empty lines.
+        let trimmed = row.trim_end_matches('.');
+        output.push_str(if trimmed.is_empty() { "." } else { trimmed });
+        output.push('\n');
+    }
+    output
+}
+
+#[path = "plaintext_test.rs"]
+#[cfg(test)]
+mod plaintext_test;
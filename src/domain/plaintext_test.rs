@@ -0,0 +1,47 @@
+use super::{parse, write};
+use crate::domain::pattern_file::{PatternFile, PatternFileError};
+
+#[test]
+fn parse_reads_name_comments_and_cells() {
+    let text = "!Name: Glider\n!Author: Richard K. Guy\n!The smallest spaceship.\n.O\n..O\nOOO\n";
+    let pattern = parse(text).unwrap();
+
+    assert_eq!(pattern.name.as_deref(), Some("Glider"));
+    assert_eq!(pattern.author.as_deref(), Some("Richard K. Guy"));
+    assert_eq!(pattern.comments, vec!["The smallest spaceship."]);
+    assert_eq!(pattern.rule, None);
+    assert_eq!(pattern.cells, vec![(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]);
+}
+
+#[test]
+fn parse_keeps_blank_rows_and_accepts_asterisks() {
+    let pattern = parse("*.*\n\n.\n.*\n").unwrap();
+    assert_eq!(pattern.cells, vec![(0, 0), (2, 0), (1, 3)]);
+}
+
+#[test]
+fn parse_rejects_unknown_characters() {
+    assert_eq!(
+        parse("!Name: x\n.O\n.X\n"),
+        Err(PatternFileError::UnexpectedCharacter {
+            line: 3,
+            character: 'X'
+        })
+    );
+}
+
+#[test]
+fn write_round_trips() {
+    let pattern = PatternFile {
+        name: Some("Beehive with tail".to_string()),
+        author: Some("Unknown".to_string()),
+        comments: vec!["still life".to_string()],
+        rule: None,
+        cells: vec![(1, 0), (2, 0), (0, 1), (3, 1), (1, 2), (2, 2), (3, 4)],
+    };
+
+    let text = write(&pattern);
+    assert!(text.starts_with("!Name: Beehive with tail\n"));
+    assert!(text.ends_with(".OO\n.\n...O\n"));
+    assert_eq!(parse(&text).unwrap(), pattern);
+}
@@ -0,0 +1,211 @@
+use std::collections::BTreeMap;
+
+use super::pattern_file::{PatternFile, PatternFileError, MAX_CELL_LIST_POPULATION};
+use super::rule::Rule;
+
+/// Body lines are wrapped before they exceed this many characters.
+const MAX_LINE_LENGTH: usize = 70;
+
+/// Parses a Run Length Encoded (`.rle`) pattern as used by Golly and LifeWiki.
+/// Header dimensions are checked but the cells are taken from the body.
+pub fn parse(text: &str) -> Result<PatternFile, PatternFileError> {
+    let mut pattern = PatternFile::default();
+    let mut lines = text.lines().enumerate();
+
+    let mut header_found = false;
+    for (_, line) in lines.by_ref() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(comment) = line.strip_prefix('#') {
+            read_comment(&mut pattern, comment);
+            continue;
+        }
+        if !line.starts_with('x') {
+            return Err(PatternFileError::MissingHeader);
+        }
+        pattern.rule = read_header(line)?;
+        header_found = true;
+        break;
+    }
+    if !header_found {
+        return Err(PatternFileError::MissingHeader);
+    }
+
+    let (mut x, mut y) = (0i32, 0i32);
+    let mut count: Option<i32> = None;
+    'body: for (index, line) in lines {
+        for character in line.chars() {
+            match character {
+                '0'..='9' => {
+                    let digit = character as i32 - '0' as i32;
+                    count = Some(
+                        count
+                            .unwrap_or(0)
+                            .checked_mul(10)
+                            .and_then(|value| value.checked_add(digit))
+                            .ok_or(PatternFileError::TooLarge)?,
+                    );
+                }
+                'b' | '.' => {
+                    x = x
+                        .checked_add(count.take().unwrap_or(1))
+                        .ok_or(PatternFileError::TooLarge)?;
+                }
+                'o' => {
+                    let run = count.take().unwrap_or(1);
+                    if pattern.cells.len() + run as usize > MAX_CELL_LIST_POPULATION {
+                        return Err(PatternFileError::TooLarge);
+                    }
+                    for _ in 0..run {
+                        pattern.cells.push((x, y));
+                        x = x.checked_add(1).ok_or(PatternFileError::TooLarge)?;
+                    }
+                }
+                '$' => {
+                    y = y
+                        .checked_add(count.take().unwrap_or(1))
+                        .ok_or(PatternFileError::TooLarge)?;
+                    x = 0;
+                }
+                '!' => break 'body,
+                c if c.is_whitespace() => {}
+                c => {
+                    return Err(PatternFileError::UnexpectedCharacter {
+                        line: index + 1,
+                        character: c,
+                    })
+                }
+            }
+        }
+    }
+
+    Ok(pattern)
+}
+
+pub fn write(pattern: &PatternFile) -> String {
+    let mut output = String::new();
+    if let Some(name) = &pattern.name {
+        output.push_str(&format!("#N {name}\n"));
+    }
+    if let Some(author) = &pattern.author {
+        output.push_str(&format!("#O {author}\n"));
+    }
+    for comment in &pattern.comments {
+        output.push_str(&format!("#C {comment}\n"));
+    }
+
+    let (width, height) = pattern.size();
+    let rule = pattern.rule.unwrap_or_default();
+    output.push_str(&format!("x = {width}, y = {height}, rule = {rule}\n"));
+
+    let mut rows: BTreeMap<i32, Vec<i32>> = BTreeMap::new();
+    for &(x, y) in &pattern.cells {
+        rows.entry(y).or_default().push(x);
+    }
+
+    let mut tokens = Vec::new();
+    let mut current_row = 0;
+    for (y, mut xs) in rows {
+        if y > current_row {
+            tokens.push(run(y - current_row, '$'));
+            current_row = y;
+        }
+        xs.sort_unstable();
+        xs.dedup();
+
+        let mut next_x = 0;
+        let mut index = 0;
+        while index < xs.len() {
+            let start = xs[index];
+            let mut end = start + 1;
+            index += 1;
+            while index < xs.len() && xs[index] == end {
+                end += 1;
+                index += 1;
+            }
+            if start > next_x {
+                tokens.push(run(start - next_x, 'b'));
+            }
+            tokens.push(run(end - start, 'o'));
+            next_x = end;
+        }
+    }
+    tokens.push("!".to_string());
+
+    let mut line_length = 0;
+    for token in tokens {
+        if line_length + token.len() > MAX_LINE_LENGTH {
+            output.push('\n');
+            line_length = 0;
+        }
+        line_length += token.len();
+        output.push_str(&token);
+    }
+    output.push('\n');
+    output
+}
+
+fn run(length: i32, tag: char) -> String {
+    if length == 1 {
+        tag.to_string()
+    } else {
+        format!("{length}{tag}")
+    }
+}
+
+fn read_comment(pattern: &mut PatternFile, comment: &str) {
+    let mut chars = comment.chars();
+    let kind = chars.next();
+    let text = chars.as_str().trim().to_string();
+    match kind {
+        Some('N') => pattern.name = Some(text),
+        Some('O') => pattern.author = Some(text),
+        Some('C') | Some('c') => pattern.comments.push(text),
+        _ => {}
+    }
+}
+
+fn read_header(line: &str) -> Result<Option<Rule>, PatternFileError> {
+    let invalid = || PatternFileError::InvalidHeader(line.to_string());
+    let mut has_x = false;
+    let mut has_y = false;
+    let mut rule = None;
+
+    // The rule is always last and may itself contain commas (`:T10,10`).
+    let (dimensions, rule_field) = match line.find("rule") {
+        Some(index) => (&line[..index], Some(&line[index..])),
+        None => (line, None),
+    };
+
+    for field in dimensions
+        .split(',')
+        .filter(|field| !field.trim().is_empty())
+    {
+        let (key, value) = field.split_once('=').ok_or_else(invalid)?;
+        let value = value.trim();
+        match key.trim() {
+            "x" => has_x = value.parse::<u64>().is_ok(),
+            "y" => has_y = value.parse::<u64>().is_ok(),
+            _ => {}
+        }
+    }
+
+    if let Some(field) = rule_field {
+        let (_, value) = field.split_once('=').ok_or_else(invalid)?;
+        // Golly appends the bounded-grid topology after a colon.
+        let rulestring = value.split(':').next().unwrap_or(value);
+        rule = Some(Rule::parse(rulestring)?);
+    }
+
+    if has_x && has_y {
+        Ok(rule)
+    } else {
+        Err(invalid())
+    }
+}
+
+#[path = "rle_test.rs"]
+#[cfg(test)]
+mod rle_test;
@@ -0,0 +1,135 @@
+use super::{parse, write};
+use crate::domain::pattern_file::{PatternFile, PatternFileError};
+use crate::domain::patterns::PATTERNS;
+use crate::domain::rule::Rule;
+
+fn sorted(mut cells: Vec<(i32, i32)>) -> Vec<(i32, i32)> {
+    cells.sort_by_key(|&(x, y)| (y, x));
+    cells
+}
+
+#[test]
+fn parse_reads_header_comments_and_cells() {
+    let text = "#N Glider\n#O Richard K. Guy\n#C The smallest spaceship.\n#C Found in 1969.\nx = 3, y = 3, rule = B3/S23\nbob$2bo$3o!\n";
+    let pattern = parse(text).unwrap();
+
+    assert_eq!(pattern.name.as_deref(), Some("Glider"));
+    assert_eq!(pattern.author.as_deref(), Some("Richard K. Guy"));
+    assert_eq!(
+        pattern.comments,
+        vec!["The smallest spaceship.", "Found in 1969."]
+    );
+    assert_eq!(pattern.rule, Some(Rule::conway()));
+    assert_eq!(pattern.cells, vec![(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]);
+}
+
+#[test]
+fn parse_handles_multi_line_runs_and_blank_rows() {
+    let text = "x = 12, y = 4\n2o\n10b$\n3$\n12o!";
+    let pattern = parse(text).unwrap();
+
+    assert_eq!(pattern.rule, None);
+    let mut expected = vec![(0, 0), (1, 0)];
+    expected.extend((0..12).map(|x| (x, 4)));
+    assert_eq!(pattern.cells, expected);
+}
+
+#[test]
+fn parse_accepts_golly_rule_suffix_and_trailing_text() {
+    let text = "x = 3, y = 1, rule = B36/S23:T10,10\n3o! this is ignored\nbbb";
+    let pattern = parse(text).unwrap();
+
+    assert_eq!(pattern.rule, Rule::parse("B36/S23").ok());
+    assert_eq!(pattern.cells, vec![(0, 0), (1, 0), (2, 0)]);
+}
+
+#[test]
+fn parse_rejects_malformed_input() {
+    assert_eq!(parse(""), Err(PatternFileError::MissingHeader));
+    assert_eq!(parse("#C only\n3o!"), Err(PatternFileError::MissingHeader));
+    assert!(matches!(
+        parse("x = 3\n3o!"),
+        Err(PatternFileError::InvalidHeader(_))
+    ));
+    assert!(matches!(
+        parse("x = 3, y = 1, rule = B9/S23\n3o!"),
+        Err(PatternFileError::InvalidRule(_))
+    ));
+    assert_eq!(
+        parse("x = 3, y = 1\n\n2oz!"),
+        Err(PatternFileError::UnexpectedCharacter {
+            line: 3,
+            character: 'z'
+        })
+    );
+}
+
+#[test]
+fn write_produces_standard_rle() {
+    let pattern = PatternFile {
+        name: Some("Glider".to_string()),
+        rule: Some(Rule::conway()),
+        cells: vec![(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)],
+        ..PatternFile::default()
+    };
+
+    assert_eq!(
+        write(&pattern),
+        "#N Glider\nx = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n"
+    );
+}
+
+#[test]
+fn write_wraps_long_lines() {
+    let cells = (0..200)
+        .filter(|x| x % 2 == 0)
+        .map(|x| (x, 0))
+        .collect::<Vec<_>>();
+    let pattern = PatternFile {
+        cells,
+        ..PatternFile::default()
+    };
+
+    let text = write(&pattern);
+    assert!(text.lines().all(|line| line.len() <= 70));
+    assert_eq!(parse(&text).unwrap().cells, pattern.cells);
+}
+
+#[test]
+fn round_trip_preserves_library_patterns() {
+    for definition in PATTERNS.iter() {
+        let min_x = definition.cells.iter().map(|&(x, _)| x).min().unwrap();
+        let min_y = definition.cells.iter().map(|&(_, y)| y).min().unwrap();
+        let mut cells: Vec<_> = definition
+            .cells
+            .iter()
+            .map(|&(x, y)| (x - min_x, y - min_y))
+            .collect();
+        cells.dedup();
+        let pattern = PatternFile {
+            name: Some(definition.name.to_string()),
+            author: Some("Test".to_string()),
+            comments: vec!["first".to_string(), "second".to_string()],
+            rule: Some(Rule::parse("B36/S23").unwrap()),
+            cells: sorted(cells),
+        };
+
+        let mut parsed = parse(&write(&pattern)).unwrap();
+        parsed.cells = sorted(parsed.cells);
+        let mut expected = pattern.clone();
+        expected.cells.dedup();
+        assert_eq!(parsed, expected, "{}", definition.name);
+    }
+}
+
+#[test]
+fn huge_runs_are_refused_before_allocating() {
+    assert_eq!(
+        parse("x = 0, y = 0\n2000000000o!").err(),
+        Some(PatternFileError::TooLarge)
+    );
+    assert_eq!(
+        parse("x = 0, y = 0\n3000000o$3000000o!").err(),
+        Some(PatternFileError::TooLarge)
+    );
+}
@@ -0,0 +1,246 @@
+use std::fmt;
+
+/// Outer-totalistic Life-like rule: a cell's next state depends only on its
+/// current state and the number of live cells among its eight neighbors.
+///
+/// Birth and survival conditions are stored as bitmasks where bit `n` is set
+/// when a neighbor count of `n` triggers the transition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rule {
+    birth: u16,
+    survival: u16,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleParseError {
+    Empty,
+    InvalidCharacter(char),
+    DuplicateSection(char),
+    MissingSection(char),
+    MissingSeparator,
+}
+
+impl fmt::Display for RuleParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleParseError::Empty => write!(f, "rulestring is empty"),
+            RuleParseError::InvalidCharacter(c) => {
+                write!(
+                    f,
+                    "unexpected character '{c}' (neighbor counts must be 0-8)"
+                )
+            }
+            RuleParseError::DuplicateSection(c) => {
+                write!(
+                    f,
+                    "section '{}' appears more than once",
+                    c.to_ascii_uppercase()
+                )
+            }
+            RuleParseError::MissingSection(c) => {
+                write!(f, "missing '{}' section", c.to_ascii_uppercase())
+            }
+            RuleParseError::MissingSeparator => {
+                write!(f, "expected survival/birth separated by a single '/'")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RuleParseError {}
+
+pub struct RulePreset {
+    pub name: &'static str,
+    pub rulestring: &'static str,
+}
+
+pub const RULE_PRESETS: [RulePreset; 10] = [
+    RulePreset {
+        name: "Conway's Life",
+        rulestring: "B3/S23",
+    },
+    RulePreset {
+        name: "HighLife",
+        rulestring: "B36/S23",
+    },
+    RulePreset {
+        name: "Day & Night",
+        rulestring: "B3678/S34678",
+    },
+    RulePreset {
+        name: "Seeds",
+        rulestring: "B2/S",
+    },
+    RulePreset {
+        name: "Life without Death",
+        rulestring: "B3/S012345678",
+    },
+    RulePreset {
+        name: "Maze",
+        rulestring: "B3/S12345",
+    },
+    RulePreset {
+        name: "Replicator",
+        rulestring: "B1357/S1357",
+    },
+    RulePreset {
+        name: "2x2",
+        rulestring: "B36/S125",
+    },
+    RulePreset {
+        name: "Morley",
+        rulestring: "B368/S245",
+    },
+    RulePreset {
+        name: "Diamoeba",
+        rulestring: "B35678/S5678",
+    },
+];
+
+impl Rule {
+    /// Conway's Game of Life (B3/S23).
+    pub const fn conway() -> Self {
+        Self {
+            birth: 1 << 3,
+            survival: (1 << 2) | (1 << 3),
+        }
+    }
+
+    /// Parses a rulestring in `B36/S23`, `b3s23` or survival/birth (`23/36`)
+    /// notation. Letters are case-insensitive and the B/S sections may appear
+    /// in either order.
+    pub fn parse(input: &str) -> Result<Self, RuleParseError> {
+        let text = input.trim().to_ascii_lowercase();
+        if text.is_empty() {
+            return Err(RuleParseError::Empty);
+        }
+
+        if text.starts_with('b') || text.starts_with('s') {
+            parse_bs_notation(&text)
+        } else {
+            parse_survival_birth_notation(&text)
+        }
+    }
+
+    pub fn births(&self, neighbor_count: u8) -> bool {
+        neighbor_count <= 8 && self.birth & (1 << neighbor_count) != 0
+    }
+
+    pub fn survives(&self, neighbor_count: u8) -> bool {
+        neighbor_count <= 8 && self.survival & (1 << neighbor_count) != 0
+    }
+
+    /// Formats the rule in survival/birth notation (`23/3`).
+    pub fn to_survival_birth(self) -> String {
+        format!(
+            "{}/{}",
+            digits_from_mask(self.survival),
+            digits_from_mask(self.birth)
+        )
+    }
+}
+
+impl Default for Rule {
+    fn default() -> Self {
+        Self::conway()
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "B{}/S{}",
+            digits_from_mask(self.birth),
+            digits_from_mask(self.survival)
+        )
+    }
+}
+
+impl std::str::FromStr for Rule {
+    type Err = RuleParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Rule::parse(s)
+    }
+}
+
+fn parse_bs_notation(text: &str) -> Result<Rule, RuleParseError> {
+    let mut birth: Option<u16> = None;
+    let mut survival: Option<u16> = None;
+    let mut current: Option<char> = None;
+    // A '/' may only separate one section from the letter of the next.
+    let mut separated = false;
+
+    for c in text.chars() {
+        match c {
+            'b' | 's' => {
+                let section = if c == 'b' { &mut birth } else { &mut survival };
+                if section.is_some() {
+                    return Err(RuleParseError::DuplicateSection(c));
+                }
+                *section = Some(0);
+                current = Some(c);
+                separated = false;
+            }
+            '/' => {
+                if current.is_none() || separated {
+                    return Err(RuleParseError::InvalidCharacter(c));
+                }
+                separated = true;
+            }
+            '0'..='8' if separated => return Err(RuleParseError::InvalidCharacter('/')),
+            '0'..='8' => {
+                let bit = 1 << (c as u8 - b'0');
+                match current {
+                    Some('b') => birth = birth.map(|mask| mask | bit),
+                    Some(_) => survival = survival.map(|mask| mask | bit),
+                    None => return Err(RuleParseError::InvalidCharacter(c)),
+                }
+            }
+            _ => return Err(RuleParseError::InvalidCharacter(c)),
+        }
+    }
+    if separated {
+        return Err(RuleParseError::InvalidCharacter('/'));
+    }
+
+    Ok(Rule {
+        birth: birth.ok_or(RuleParseError::MissingSection('b'))?,
+        survival: survival.ok_or(RuleParseError::MissingSection('s'))?,
+    })
+}
+
+fn parse_survival_birth_notation(text: &str) -> Result<Rule, RuleParseError> {
+    let mut parts = text.split('/');
+    let (Some(survival), Some(birth), None) = (parts.next(), parts.next(), parts.next()) else {
+        return Err(RuleParseError::MissingSeparator);
+    };
+
+    Ok(Rule {
+        birth: parse_digits(birth)?,
+        survival: parse_digits(survival)?,
+    })
+}
+
+fn parse_digits(text: &str) -> Result<u16, RuleParseError> {
+    let mut mask = 0u16;
+    for c in text.chars() {
+        match c {
+            '0'..='8' => mask |= 1 << (c as u8 - b'0'),
+            _ => return Err(RuleParseError::InvalidCharacter(c)),
+        }
+    }
+    Ok(mask)
+}
+
+fn digits_from_mask(mask: u16) -> String {
+    (0..=8u8)
+        .filter(|count| mask & (1 << count) != 0)
+        .map(|count| char::from(b'0' + count))
+        .collect()
+}
+
+#[path = "rule_test.rs"]
+#[cfg(test)]
+mod rule_test;
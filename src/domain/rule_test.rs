@@ -0,0 +1,91 @@
+use super::{Rule, RuleParseError, RULE_PRESETS};
+
+#[test]
+fn parse_accepts_bs_notation() {
+    let rule = Rule::parse("B36/S23").unwrap();
+    assert!(rule.births(3));
+    assert!(rule.births(6));
+    assert!(!rule.births(2));
+    assert!(rule.survives(2));
+    assert!(rule.survives(3));
+    assert!(!rule.survives(6));
+}
+
+#[test]
+fn parse_accepts_compact_lowercase_notation() {
+    assert_eq!(Rule::parse("b3s23").unwrap(), Rule::conway());
+    assert_eq!(Rule::parse("  B3/S23 ").unwrap(), Rule::conway());
+    assert_eq!(Rule::parse("S23/B3").unwrap(), Rule::conway());
+}
+
+#[test]
+fn parse_accepts_survival_birth_notation() {
+    assert_eq!(Rule::parse("23/3").unwrap(), Rule::conway());
+    assert_eq!(
+        Rule::parse("23/36").unwrap(),
+        Rule::parse("B36/S23").unwrap()
+    );
+}
+
+#[test]
+fn parse_allows_empty_sections() {
+    let seeds = Rule::parse("B2/S").unwrap();
+    assert!(seeds.births(2));
+    for count in 0..=8 {
+        assert!(!seeds.survives(count));
+    }
+    assert_eq!(Rule::parse("/2").unwrap(), seeds);
+}
+
+#[test]
+fn parse_rejects_invalid_input() {
+    assert_eq!(Rule::parse(""), Err(RuleParseError::Empty));
+    assert_eq!(
+        Rule::parse("B39/S23"),
+        Err(RuleParseError::InvalidCharacter('9'))
+    );
+    assert_eq!(
+        Rule::parse("B3/S23/B6"),
+        Err(RuleParseError::DuplicateSection('b'))
+    );
+    assert_eq!(Rule::parse("B3"), Err(RuleParseError::MissingSection('s')));
+    assert_eq!(Rule::parse("23"), Err(RuleParseError::MissingSeparator));
+    assert_eq!(Rule::parse("2/3/4"), Err(RuleParseError::MissingSeparator));
+    assert_eq!(
+        Rule::parse("B3/S2x"),
+        Err(RuleParseError::InvalidCharacter('x'))
+    );
+}
+
+#[test]
+fn parse_rejects_stray_separators() {
+    for bad in ["B3//S23", "B3/S23/", "B3/3S23", "S23/B3/", "b3s23//"] {
+        assert_eq!(
+            Rule::parse(bad),
+            Err(RuleParseError::InvalidCharacter('/')),
+            "{bad}"
+        );
+    }
+}
+
+#[test]
+fn display_uses_canonical_bs_notation() {
+    assert_eq!(Rule::conway().to_string(), "B3/S23");
+    assert_eq!(Rule::parse("s32b63").unwrap().to_string(), "B36/S23");
+    assert_eq!(Rule::parse("B2/S").unwrap().to_string(), "B2/S");
+}
+
+#[test]
+fn presets_are_valid_and_round_trip() {
+    for preset in RULE_PRESETS.iter() {
+        let rule = Rule::parse(preset.rulestring).unwrap();
+        assert_eq!(rule.to_string(), preset.rulestring, "{}", preset.name);
+    }
+}
+
+#[test]
+fn to_survival_birth_round_trips() {
+    let rule = Rule::parse("B36/S23").unwrap();
+    assert_eq!(rule.to_survival_birth(), "23/36");
+    assert_eq!(Rule::parse(&rule.to_survival_birth()).unwrap(), rule);
+}
@@ -1,6 +1,18 @@
-use super::grid::Grid;
+use std::collections::HashMap;
 
-pub fn count_neighbors(grid: &Grid, x: usize, y: usize) -> u8 {
+use rayon::prelude::*;
+
+use super::board::Board;
+use super::cell::Cell;
+use super::grid::{Grid, WORD_BITS};
+use super::rule::Rule;
+use super::sparse_grid::SparseGrid;
+use super::topology::Topology;
+
+/// Rows each parallel task steps at once.
+const BAND_ROWS: usize = 16;
+
+pub fn count_neighbors(grid: &Grid, x: usize, y: usize, topology: Topology) -> u8 {
     let mut count = 0u8;
 
     for dy in -1i32..=1 {
@@ -12,13 +24,12 @@ pub fn count_neighbors(grid: &Grid, x: usize, y: usize) -> u8 {
             let nx = x as i32 + dx;
             let ny = y as i32 + dy;
 
-            if nx >= 0 && ny >= 0 {
-                let nx = nx as usize;
-                let ny = ny as usize;
-
-                if nx < grid.width() && ny < grid.height() && grid.get(nx, ny).is_alive() {
-                    count += 1;
-                }
+            let neighbor = match topology.wrap(nx, ny, grid.width(), grid.height()) {
+                Some((nx, ny)) => grid.get(nx, ny),
+                None => topology.outside_cell(),
+            };
+            if neighbor.is_alive() {
+                count += 1;
             }
         }
     }
@@ -26,14 +37,16 @@ pub fn count_neighbors(grid: &Grid, x: usize, y: usize) -> u8 {
     count
 }
 
-pub fn next_generation(grid: &Grid) -> Grid {
+/// Reference stepper that counts each cell's neighbors one at a time. It is
+/// kept to check and benchmark `next_generation` against.
+pub fn next_generation_per_cell(grid: &Grid, rule: &Rule, topology: Topology) -> Grid {
     let mut new_grid = Grid::new(grid.width(), grid.height());
 
     for y in 0..grid.height() {
         for x in 0..grid.width() {
-            let neighbors = count_neighbors(grid, x, y);
+            let neighbors = count_neighbors(grid, x, y, topology);
             let cell = grid.get(x, y);
-            let new_cell = cell.next_state(neighbors);
+            let new_cell = cell.next_state(neighbors, rule);
             new_grid.set(x, y, new_cell);
         }
     }
@@ -41,6 +54,243 @@ pub fn next_generation(grid: &Grid) -> Grid {
     new_grid
 }
 
+/// Advances a grid by one generation, 64 cells at a time: the eight
+/// neighbors of each bit are summed with bitwise adders into four bit planes,
+/// and the rule is applied by matching those planes against its counts.
+pub fn next_generation(grid: &Grid, rule: &Rule, topology: Topology) -> Grid {
+    let mut next = Grid::new(grid.width(), grid.height());
+    if grid.width() == 0 || grid.height() == 0 {
+        return next;
+    }
+
+    let kernel = Kernel::new(grid, rule, topology);
+    let mut scratch = vec![0u64; kernel.words];
+    let words_per_row = next.words_per_row();
+    for (y, target) in next.words_mut().chunks_mut(words_per_row).enumerate() {
+        kernel.step_row(y, target, &mut scratch);
+    }
+    next
+}
+
+/// Same result as `next_generation`, with bands of `BAND_ROWS` rows stepped
+/// on the rayon thread pool. Worth it for large boards only.
+pub fn next_generation_parallel(grid: &Grid, rule: &Rule, topology: Topology) -> Grid {
+    let mut next = Grid::new(grid.width(), grid.height());
+    if grid.width() == 0 || grid.height() == 0 {
+        return next;
+    }
+
+    let kernel = Kernel::new(grid, rule, topology);
+    let words_per_row = next.words_per_row();
+    next.words_mut()
+        .par_chunks_mut(words_per_row * BAND_ROWS)
+        .enumerate()
+        .for_each(|(band, rows)| {
+            let mut scratch = vec![0u64; kernel.words];
+            for (offset, target) in rows.chunks_mut(words_per_row).enumerate() {
+                kernel.step_row(band * BAND_ROWS + offset, target, &mut scratch);
+            }
+        });
+    next
+}
+
+/// Which grid stepper to run; both produce identical generations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Stepper {
+    /// One thread; fastest for small boards.
+    #[default]
+    Serial,
+    /// Row bands spread over every core.
+    Parallel,
+}
+
+pub const STEPPERS: [Stepper; 2] = [Stepper::Serial, Stepper::Parallel];
+
+impl Stepper {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Stepper::Serial => "Serial",
+            Stepper::Parallel => "Parallel",
+        }
+    }
+
+    pub fn step(&self, grid: &Grid, rule: &Rule, topology: Topology) -> Grid {
+        match self {
+            Stepper::Serial => next_generation(grid, rule, topology),
+            Stepper::Parallel => next_generation_parallel(grid, rule, topology),
+        }
+    }
+}
+
+/// A grid padded by its topology plus the rule's counts, shared by every row.
+struct Kernel {
+    padded: Vec<u64>,
+    /// Words per padded row.
+    words: usize,
+    width: usize,
+    births: Vec<u8>,
+    survivals: Vec<u8>,
+}
+
+impl Kernel {
+    fn new(grid: &Grid, rule: &Rule, topology: Topology) -> Self {
+        Self {
+            padded: padded_rows(grid, topology),
+            words: padded_words(grid.width()),
+            width: grid.width(),
+            births: (0..=8).filter(|&count| rule.births(count)).collect(),
+            survivals: (0..=8).filter(|&count| rule.survives(count)).collect(),
+        }
+    }
+
+    /// Writes generation `y + 1` of grid row `y` into `target`; `scratch`
+    /// holds one padded row.
+    fn step_row(&self, y: usize, target: &mut [u64], scratch: &mut [u64]) {
+        let words = self.words;
+        let above = &self.padded[y * words..(y + 1) * words];
+        let row = &self.padded[(y + 1) * words..(y + 2) * words];
+        let below = &self.padded[(y + 2) * words..(y + 3) * words];
+
+        for (index, out) in scratch.iter_mut().enumerate() {
+            let mut planes = [0u64; 4];
+            for source in [above, below] {
+                add_bits(&mut planes, west(source, index));
+                add_bits(&mut planes, source[index]);
+                add_bits(&mut planes, east(source, index));
+            }
+            add_bits(&mut planes, west(row, index));
+            add_bits(&mut planes, east(row, index));
+
+            let alive = row[index];
+            let born = self
+                .births
+                .iter()
+                .fold(0, |mask, &count| mask | count_equals(&planes, count));
+            let survived = self
+                .survivals
+                .iter()
+                .fold(0, |mask, &count| mask | count_equals(&planes, count));
+            *out = (alive & survived) | (!alive & born);
+        }
+
+        // Drop the padding column on the left and anything past the width.
+        for (index, word) in target.iter_mut().enumerate() {
+            *word = east(scratch, index);
+        }
+        if !self.width.is_multiple_of(WORD_BITS) {
+            if let Some(last) = target.last_mut() {
+                *last &= (1 << (self.width % WORD_BITS)) - 1;
+            }
+        }
+    }
+}
+
+/// Words per row of a grid `width` cells wide plus a padding column on each side.
+fn padded_words(width: usize) -> usize {
+    (width + 2).div_ceil(WORD_BITS)
+}
+
+/// Copies the grid into rows one cell larger on every side, filling the
+/// border from the topology so the kernel never needs to know about edges.
+/// Bit `x + 1` of padded row `y + 1` holds cell `(x, y)`.
+fn padded_rows(grid: &Grid, topology: Topology) -> Vec<u64> {
+    let (width, height) = (grid.width(), grid.height());
+    let words = padded_words(width);
+    let mut padded = vec![0u64; words * (height + 2)];
+    let outside = |x: i32, y: i32| match topology.wrap(x, y, width, height) {
+        Some((x, y)) => grid.get(x, y),
+        None => topology.outside_cell(),
+    };
+    let set = |padded: &mut [u64], x: i32, y: i32| {
+        if outside(x, y).is_alive() {
+            let (column, row) = ((x + 1) as usize, (y + 1) as usize);
+            padded[row * words + column / WORD_BITS] |= 1 << (column % WORD_BITS);
+        }
+    };
+
+    for y in 0..height {
+        let source = grid.row(y);
+        let target = &mut padded[(y + 1) * words..(y + 2) * words];
+        for (index, word) in target.iter_mut().enumerate() {
+            let low = index
+                .checked_sub(1)
+                .map_or(0, |previous| source[previous] >> 63);
+            let high = source.get(index).map_or(0, |&word| word << 1);
+            *word = high | low;
+        }
+        set(&mut padded, -1, y as i32);
+        set(&mut padded, width as i32, y as i32);
+    }
+    for x in -1..=width as i32 {
+        set(&mut padded, x, -1);
+        set(&mut padded, x, height as i32);
+    }
+    padded
+}
+
+/// Bit `j` of the result is bit `j - 1` of the row: each cell's west neighbor.
+fn west(row: &[u64], index: usize) -> u64 {
+    let carry = index
+        .checked_sub(1)
+        .map_or(0, |previous| row[previous] >> 63);
+    (row[index] << 1) | carry
+}
+
+/// Bit `j` of the result is bit `j + 1` of the row: each cell's east neighbor.
+fn east(row: &[u64], index: usize) -> u64 {
+    let carry = row.get(index + 1).map_or(0, |next| next << 63);
+    (row[index] >> 1) | carry
+}
+
+/// Adds one input bit to each lane of a four-bit counter stored as planes.
+fn add_bits(planes: &mut [u64; 4], bits: u64) {
+    let mut carry = bits;
+    for plane in planes.iter_mut() {
+        let next_carry = *plane & carry;
+        *plane ^= carry;
+        carry = next_carry;
+    }
+}
+
+/// Lanes whose counter equals `count`.
+fn count_equals(planes: &[u64; 4], count: u8) -> u64 {
+    planes
+        .iter()
+        .enumerate()
+        .fold(u64::MAX, |mask, (bit, &plane)| {
+            mask & if count >> bit & 1 == 1 { plane } else { !plane }
+        })
+}
+
+/// Advances an unbounded plane by one generation. Only cells next to a live
+/// cell are considered, so births on zero neighbors (B0) never happen.
+pub fn next_generation_unbounded(plane: &SparseGrid, rule: &Rule) -> SparseGrid {
+    let mut neighbor_counts: HashMap<(i64, i64), u8> = HashMap::new();
+    for (x, y) in plane.live_cells() {
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                if dx != 0 || dy != 0 {
+                    *neighbor_counts.entry((x + dx, y + dy)).or_insert(0) += 1;
+                }
+            }
+        }
+    }
+
+    let mut next = SparseGrid::new();
+    for (x, y) in plane.live_cells() {
+        if !neighbor_counts.contains_key(&(x, y)) && rule.survives(0) {
+            next.set(x, y, Cell::Alive);
+        }
+    }
+    for ((x, y), neighbors) in neighbor_counts {
+        if plane.get(x, y).next_state(neighbors, rule).is_alive() {
+            next.set(x, y, Cell::Alive);
+        }
+    }
+
+    next
+}
+
 #[path = "rules_test.rs"]
 #[cfg(test)]
 mod rules_test;
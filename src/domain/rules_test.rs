@@ -1,5 +1,15 @@
-use super::{count_neighbors, next_generation, Grid};
+use proptest::prelude::*;
+
+use super::{
+    count_neighbors, next_generation, next_generation_parallel, next_generation_per_cell,
+    next_generation_unbounded, Grid,
+};
+use crate::domain::board::{Board, Bounds};
 use crate::domain::cell::Cell;
+use crate::domain::rule::{Rule, RULE_PRESETS};
+use crate::domain::soup::Soup;
+use crate::domain::sparse_grid::SparseGrid;
+use crate::domain::topology::{Topology, TOPOLOGIES};
 
 #[test]
 fn count_neighbors_handles_corner_cells() {
@@ -8,7 +18,7 @@ fn count_neighbors_handles_corner_cells() {
     grid.set(1, 0, Cell::Alive);
     grid.set(1, 1, Cell::Alive);
 
-    assert_eq!(count_neighbors(&grid, 0, 0), 3);
+    assert_eq!(count_neighbors(&grid, 0, 0, Topology::Bounded), 3);
 }
 
 #[test]
@@ -20,7 +30,7 @@ fn count_neighbors_handles_edge_cells() {
     grid.set(1, 1, Cell::Alive);
     grid.set(2, 1, Cell::Alive);
 
-    assert_eq!(count_neighbors(&grid, 1, 0), 5);
+    assert_eq!(count_neighbors(&grid, 1, 0, Topology::Bounded), 5);
 }
 
 #[test]
@@ -35,7 +45,7 @@ fn count_neighbors_handles_center_cells() {
         }
     }
 
-    assert_eq!(count_neighbors(&grid, 1, 1), 8);
+    assert_eq!(count_neighbors(&grid, 1, 1, Topology::Bounded), 8);
 }
 
 #[test]
@@ -45,7 +55,7 @@ fn next_generation_blinker_oscillates() {
     grid.set(2, 2, Cell::Alive);
     grid.set(3, 2, Cell::Alive);
 
-    let next = next_generation(&grid);
+    let next = next_generation(&grid, &Rule::conway(), Topology::Bounded);
     assert_eq!(next.get(2, 1), Cell::Alive);
     assert_eq!(next.get(2, 2), Cell::Alive);
     assert_eq!(next.get(2, 3), Cell::Alive);
@@ -61,7 +71,7 @@ fn next_generation_block_is_stable() {
     grid.set(2, 1, Cell::Alive);
     grid.set(2, 2, Cell::Alive);
 
-    let next = next_generation(&grid);
+    let next = next_generation(&grid, &Rule::conway(), Topology::Bounded);
     for y in 1..=2 {
         for x in 1..=2 {
             assert_eq!(next.get(x, y), Cell::Alive);
@@ -80,7 +90,7 @@ fn next_generation_glider_moves_diagonally() {
 
     let mut current = grid.clone();
     for _ in 0..4 {
-        current = next_generation(&current);
+        current = next_generation(&current, &Rule::conway(), Topology::Bounded);
     }
 
     assert_eq!(current.get(2, 1), Cell::Alive);
@@ -89,3 +99,170 @@ fn next_generation_glider_moves_diagonally() {
     assert_eq!(current.get(2, 3), Cell::Alive);
     assert_eq!(current.get(3, 3), Cell::Alive);
 }
+
+#[test]
+fn count_neighbors_bounded_ignores_opposite_edge() {
+    let mut grid = Grid::new(4, 4);
+    grid.set(3, 3, Cell::Alive);
+    grid.set(3, 0, Cell::Alive);
+    grid.set(0, 3, Cell::Alive);
+
+    assert_eq!(count_neighbors(&grid, 0, 0, Topology::Bounded), 0);
+}
+
+#[test]
+fn count_neighbors_torus_wraps_both_axes() {
+    let mut grid = Grid::new(4, 4);
+    grid.set(3, 3, Cell::Alive);
+    grid.set(3, 0, Cell::Alive);
+    grid.set(0, 3, Cell::Alive);
+
+    assert_eq!(count_neighbors(&grid, 0, 0, Topology::Torus), 3);
+}
+
+#[test]
+fn count_neighbors_klein_bottle_mirrors_across_vertical_wrap() {
+    let mut grid = Grid::new(4, 4);
+    grid.set(2, 3, Cell::Alive);
+
+    // Row -1 above (0, 0) maps to the bottom row with x mirrored: x = 0, 3, 2.
+    assert_eq!(count_neighbors(&grid, 0, 0, Topology::KleinBottle), 1);
+    assert_eq!(count_neighbors(&grid, 0, 0, Topology::Torus), 0);
+
+    // Left/right edges wrap without mirroring.
+    let mut grid = Grid::new(4, 4);
+    grid.set(3, 1, Cell::Alive);
+    assert_eq!(count_neighbors(&grid, 0, 1, Topology::KleinBottle), 1);
+}
+
+#[test]
+fn count_neighbors_cross_surface_mirrors_both_axes() {
+    let mut grid = Grid::new(4, 4);
+    grid.set(3, 3, Cell::Alive);
+
+    // Column -1 left of (0, 1) maps to the right column with y mirrored: y = 3, 2, 1.
+    assert_eq!(count_neighbors(&grid, 0, 1, Topology::CrossSurface), 1);
+    assert_eq!(count_neighbors(&grid, 0, 1, Topology::KleinBottle), 0);
+
+    let mut grid = Grid::new(4, 4);
+    grid.set(2, 3, Cell::Alive);
+    assert_eq!(count_neighbors(&grid, 1, 0, Topology::CrossSurface), 1);
+}
+
+#[test]
+fn count_neighbors_alive_border_counts_outside_cells() {
+    let grid = Grid::new(4, 4);
+
+    assert_eq!(count_neighbors(&grid, 0, 0, Topology::AliveBorder), 5);
+    assert_eq!(count_neighbors(&grid, 1, 0, Topology::AliveBorder), 3);
+    assert_eq!(count_neighbors(&grid, 1, 1, Topology::AliveBorder), 0);
+}
+
+#[test]
+fn next_generation_glider_survives_torus_wrap() {
+    let mut grid = Grid::new(8, 8);
+    grid.set(1, 0, Cell::Alive);
+    grid.set(2, 1, Cell::Alive);
+    grid.set(0, 2, Cell::Alive);
+    grid.set(1, 2, Cell::Alive);
+    grid.set(2, 2, Cell::Alive);
+
+    // A glider moves one cell diagonally every four generations, so after
+    // 4 * 8 generations it has travelled once around the torus.
+    let mut current = grid.clone();
+    for _ in 0..32 {
+        current = next_generation(&current, &Rule::conway(), Topology::Torus);
+    }
+
+    for y in 0..8 {
+        for x in 0..8 {
+            assert_eq!(current.get(x, y), grid.get(x, y), "cell ({x}, {y})");
+        }
+    }
+}
+
+#[test]
+fn next_generation_unbounded_lets_glider_escape() {
+    let mut plane = SparseGrid::new();
+    for &(x, y) in &[(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)] {
+        plane.set(x, y, Cell::Alive);
+    }
+
+    let mut current = plane;
+    for _ in 0..400 {
+        current = next_generation_unbounded(&current, &Rule::conway());
+    }
+
+    // 400 generations move the glider 100 cells, far past any 10x10 grid.
+    assert_eq!(current.population(), 5);
+    for &(x, y) in &[(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)] {
+        assert_eq!(current.get(x + 100, y + 100), Cell::Alive);
+    }
+}
+
+#[test]
+fn next_generation_unbounded_matches_bounded_away_from_edges() {
+    let mut grid = Grid::new(12, 12);
+    for &(x, y) in &[(5, 4), (6, 4), (4, 5), (5, 5), (5, 6)] {
+        grid.set(x, y, Cell::Alive);
+    }
+    let plane = SparseGrid::from_board(&grid);
+
+    let bounded = next_generation(&grid, &Rule::conway(), Topology::Bounded);
+    let unbounded = next_generation_unbounded(&plane, &Rule::conway());
+
+    let mut expected: Vec<_> = bounded.live_cells().collect();
+    let mut actual: Vec<_> = unbounded.live_cells().collect();
+    expected.sort();
+    actual.sort();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn bit_packed_step_matches_per_cell_step() {
+    let rules = ["B3/S23", "B36/S23", "B2/S", "B0/S8", "B3678/S34678"];
+    for (width, height) in [(1, 1), (5, 3), (63, 7), (64, 9), (65, 4), (130, 12)] {
+        for (seed, rule) in rules.iter().enumerate() {
+            let rule = Rule::parse(rule).unwrap();
+            let mut grid = Grid::new(width, height);
+            let soup = Soup {
+                seed: seed as u64,
+                density: 0.4,
+                ..Soup::default()
+            };
+            soup.fill(&mut grid, Bounds::new(0, 0, width as i64, height as i64));
+
+            for topology in TOPOLOGIES {
+                let expected = next_generation_per_cell(&grid, &rule, topology);
+                let actual = next_generation(&grid, &rule, topology);
+                assert!(
+                    actual == expected,
+                    "{width}x{height} {rule} {}",
+                    topology.name()
+                );
+            }
+        }
+    }
+}
+
+proptest! {
+    #[test]
+    fn parallel_step_matches_serial_step(
+        width in 1usize..150,
+        height in 1usize..70,
+        seed: u64,
+        density in 0.0..=1.0f64,
+        topology in proptest::sample::select(TOPOLOGIES.to_vec()),
+        preset in proptest::sample::select(RULE_PRESETS.map(|preset| preset.rulestring).to_vec()),
+    ) {
+        let rule = Rule::parse(preset).unwrap();
+        let mut grid = Grid::new(width, height);
+        Soup { seed, density, ..Soup::default() }
+            .fill(&mut grid, Bounds::new(0, 0, width as i64, height as i64));
+
+        let serial = next_generation(&grid, &rule, topology);
+        let parallel = next_generation_parallel(&grid, &rule, topology);
+
+        prop_assert!(parallel == serial);
+    }
+}
@@ -0,0 +1,137 @@
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use super::board::{Board, Bounds};
+use super::cell::Cell;
+
+/// Symmetry imposed on a random soup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Symmetry {
+    /// No symmetry.
+    #[default]
+    C1,
+    /// Unchanged by a half turn.
+    C2,
+    /// Unchanged by a quarter turn; the region is trimmed to a square.
+    C4,
+    /// Mirrored left to right.
+    D2,
+    /// Mirrored left to right and top to bottom.
+    D4,
+    /// Every rotation and reflection; the region is trimmed to a square.
+    D8,
+}
+
+pub const SYMMETRIES: [Symmetry; 6] = [
+    Symmetry::C1,
+    Symmetry::C2,
+    Symmetry::C4,
+    Symmetry::D2,
+    Symmetry::D4,
+    Symmetry::D8,
+];
+
+impl Symmetry {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Symmetry::C1 => "C1 (none)",
+            Symmetry::C2 => "C2 (half turn)",
+            Symmetry::C4 => "C4 (quarter turn)",
+            Symmetry::D2 => "D2 (mirror)",
+            Symmetry::D4 => "D4 (two mirrors)",
+            Symmetry::D8 => "D8 (all)",
+        }
+    }
+
+    fn needs_square(&self) -> bool {
+        matches!(self, Symmetry::C4 | Symmetry::D8)
+    }
+
+    /// Positions that must share the state of `(u, v)` in a `w` x `h` region.
+    fn images(&self, u: i64, v: i64, w: i64, h: i64) -> Vec<(i64, i64)> {
+        let (mirror_u, mirror_v) = (w - 1 - u, h - 1 - v);
+        match self {
+            Symmetry::C1 => vec![(u, v)],
+            Symmetry::C2 => vec![(u, v), (mirror_u, mirror_v)],
+            Symmetry::C4 => vec![(u, v), (mirror_v, u), (mirror_u, mirror_v), (v, mirror_u)],
+            Symmetry::D2 => vec![(u, v), (mirror_u, v)],
+            Symmetry::D4 => vec![(u, v), (mirror_u, v), (u, mirror_v), (mirror_u, mirror_v)],
+            Symmetry::D8 => vec![
+                (u, v),
+                (mirror_v, u),
+                (mirror_u, mirror_v),
+                (v, mirror_u),
+                (mirror_u, v),
+                (u, mirror_v),
+                (v, u),
+                (mirror_v, mirror_u),
+            ],
+        }
+    }
+}
+
+/// Recipe for a reproducible random soup: the same seed, density and
+/// symmetry always fill a region with the same cells, on every build, since
+/// both the generator and the way its output becomes cells are fixed here.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Soup {
+    pub seed: u64,
+    /// Probability of each independent cell being alive, from 0 to 1.
+    pub density: f64,
+    pub symmetry: Symmetry,
+}
+
+impl Default for Soup {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            density: 0.3,
+            symmetry: Symmetry::C1,
+        }
+    }
+}
+
+impl Soup {
+    /// Overwrites every cell of `region` with the soup. Square symmetries
+    /// only fill the largest square centred in the region.
+    pub fn fill(&self, board: &mut dyn Board, region: Bounds) {
+        let region = if self.symmetry.needs_square() {
+            let side = region.width().min(region.height());
+            Bounds::new(
+                region.left + (region.width() - side) / 2,
+                region.top + (region.height() - side) / 2,
+                side,
+                side,
+            )
+        } else {
+            region
+        };
+        let (w, h) = (region.width(), region.height());
+        let density = self.density.clamp(0.0, 1.0);
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed);
+
+        for v in 0..h {
+            for u in 0..w {
+                let images = self.symmetry.images(u, v, w, h);
+                // Each orbit is decided once, at its first cell in row-major order.
+                if images.iter().any(|&(iu, iv)| (iv, iu) < (v, u)) {
+                    continue;
+                }
+                // The top 53 bits as a uniform value in [0, 1).
+                let sample = (rng.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+                let cell = if sample < density {
+                    Cell::Alive
+                } else {
+                    Cell::Dead
+                };
+                for (iu, iv) in images {
+                    board.set(region.left + iu, region.top + iv, cell);
+                }
+            }
+        }
+    }
+}
+
+#[path = "soup_test.rs"]
+#[cfg(test)]
+mod soup_test;
@@ -0,0 +1,125 @@
+use super::{Soup, Symmetry, SYMMETRIES};
+use crate::domain::board::{Board, Bounds};
+use crate::domain::cell::Cell;
+use crate::domain::grid::Grid;
+use crate::domain::sparse_grid::SparseGrid;
+
+fn soup(seed: u64, symmetry: Symmetry) -> Soup {
+    Soup {
+        seed,
+        density: 0.5,
+        symmetry,
+    }
+}
+
+fn filled_grid(soup: &Soup, width: usize, height: usize) -> Grid {
+    let mut grid = Grid::new(width, height);
+    soup.fill(&mut grid, Bounds::new(0, 0, width as i64, height as i64));
+    grid
+}
+
+fn cells(board: &dyn Board) -> Vec<(i64, i64)> {
+    board.live_cells().collect()
+}
+
+#[test]
+fn identical_seeds_produce_identical_grids() {
+    for symmetry in SYMMETRIES {
+        let first = filled_grid(&soup(42, symmetry), 30, 20);
+        let second = filled_grid(&soup(42, symmetry), 30, 20);
+        assert_eq!(cells(&first), cells(&second), "{}", symmetry.name());
+    }
+}
+
+#[test]
+fn different_seeds_produce_different_grids() {
+    let first = filled_grid(&soup(1, Symmetry::C1), 30, 20);
+    let second = filled_grid(&soup(2, Symmetry::C1), 30, 20);
+    assert_ne!(cells(&first), cells(&second));
+}
+
+#[test]
+fn density_controls_how_many_cells_live() {
+    let mut empty = soup(7, Symmetry::C1);
+    empty.density = 0.0;
+    assert_eq!(filled_grid(&empty, 16, 16).population(), 0);
+
+    let mut full = soup(7, Symmetry::C1);
+    full.density = 1.0;
+    assert_eq!(filled_grid(&full, 16, 16).population(), 256);
+
+    let mut sparse = soup(7, Symmetry::C1);
+    sparse.density = 0.1;
+    let population = filled_grid(&sparse, 100, 100).population();
+    assert!((500..1500).contains(&population), "{population}");
+}
+
+#[test]
+fn fill_only_touches_region() {
+    let mut plane = SparseGrid::new();
+    plane.set(100, 100, Cell::Alive);
+    let region = Bounds::new(-5, -5, 10, 10);
+    soup(3, Symmetry::C1).fill(&mut plane, region);
+
+    assert!(plane.population() > 1);
+    assert_eq!(plane.get(100, 100), Cell::Alive);
+    assert!(plane
+        .live_cells()
+        .all(|(x, y)| region.contains(x, y) || (x, y) == (100, 100)));
+}
+
+#[test]
+fn fill_overwrites_existing_cells_in_region() {
+    let mut grid = Grid::new(4, 4);
+    grid.set(1, 1, Cell::Alive);
+    let mut empty = soup(3, Symmetry::C1);
+    empty.density = 0.0;
+
+    empty.fill(&mut grid, Bounds::new(0, 0, 4, 4));
+
+    assert_eq!(grid.population(), 0);
+}
+
+#[test]
+fn symmetric_soups_match_their_images() {
+    let (w, h) = (11usize, 8usize);
+    let c2 = filled_grid(&soup(5, Symmetry::C2), w, h);
+    let d4 = filled_grid(&soup(5, Symmetry::D4), w, h);
+    for y in 0..h {
+        for x in 0..w {
+            assert_eq!(c2.get(x, y), c2.get(w - 1 - x, h - 1 - y));
+            assert_eq!(d4.get(x, y), d4.get(w - 1 - x, y));
+            assert_eq!(d4.get(x, y), d4.get(x, h - 1 - y));
+        }
+    }
+    assert!(c2.population() > 0);
+}
+
+#[test]
+fn square_symmetries_fill_a_centred_square() {
+    let grid = filled_grid(&soup(9, Symmetry::D8), 12, 8);
+    // An 8 x 8 square starting at column 2.
+    for y in 0..8 {
+        assert_eq!(grid.get(0, y), Cell::Dead);
+        assert_eq!(grid.get(11, y), Cell::Dead);
+        for x in 0..8 {
+            let cell = grid.get(2 + x, y);
+            assert_eq!(cell, grid.get(2 + y, x));
+            assert_eq!(cell, grid.get(2 + 7 - y, x));
+            assert_eq!(cell, grid.get(2 + 7 - x, y));
+        }
+    }
+    assert!(grid.population() > 0);
+}
+
+/// Seeds are shown and logged for reproduction, so the generator's output
+/// must never change.
+#[test]
+fn known_seed_keeps_its_cells() {
+    let grid = filled_grid(&soup(42, Symmetry::C1), 8, 2);
+
+    assert_eq!(
+        cells(&grid),
+        vec![(2, 0), (4, 0), (5, 0), (6, 0), (1, 1), (7, 1)]
+    );
+}
@@ -0,0 +1,114 @@
+use std::fmt;
+
+use super::board::Board;
+use super::cell::Cell;
+use super::rule::Rule;
+use super::rules::next_generation_unbounded;
+use super::sparse_grid::SparseGrid;
+
+/// Live cells shifted so their bounding box starts at `(0, 0)`, in row-major
+/// order. Any two translated copies of a pattern share the same shape.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Shape {
+    pub cells: Vec<(i64, i64)>,
+}
+
+/// Splits a set of live cells into its translation-invariant shape and the
+/// top-left corner of its bounding box (`(0, 0)` when empty).
+pub fn canonical_form(cells: impl IntoIterator<Item = (i64, i64)>) -> (Shape, (i64, i64)) {
+    let mut cells: Vec<(i64, i64)> = cells.into_iter().collect();
+    let left = cells.iter().map(|&(x, _)| x).min().unwrap_or(0);
+    let top = cells.iter().map(|&(_, y)| y).min().unwrap_or(0);
+    for (x, y) in &mut cells {
+        *x -= left;
+        *y -= top;
+    }
+    cells.sort_unstable_by_key(|&(x, y)| (y, x));
+    (Shape { cells }, (left, top))
+}
+
+/// How far a pattern travels each time it repeats. Still lifes and
+/// oscillators have a zero displacement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Velocity {
+    pub period: u64,
+    pub dx: i64,
+    pub dy: i64,
+}
+
+impl Velocity {
+    pub fn is_moving(&self) -> bool {
+        self.dx != 0 || self.dy != 0
+    }
+}
+
+/// Conventional speed notation: `c/4 diagonal`, `c/2 orthogonal` or
+/// `(2,1)c/6 oblique`.
+impl fmt::Display for Velocity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (a, b) = (self.dx.unsigned_abs(), self.dy.unsigned_abs());
+        let (far, near) = (a.max(b), a.min(b));
+        if far == 0 {
+            return write!(f, "stationary (period {})", self.period);
+        }
+        if near != 0 && near != far {
+            return write!(f, "({far},{near})c/{} oblique", self.period);
+        }
+
+        let divisor = gcd(far, self.period);
+        let (distance, period) = (far / divisor, self.period / divisor);
+        let numerator = if distance == 1 {
+            String::new()
+        } else {
+            distance.to_string()
+        };
+        let direction = if near == 0 { "orthogonal" } else { "diagonal" };
+        if period == 1 {
+            write!(f, "{numerator}c {direction}")
+        } else {
+            write!(f, "{numerator}c/{period} {direction}")
+        }
+    }
+}
+
+/// Runs `cells` on the unbounded plane until its shape first recurs, within
+/// `max_period` generations, and reports the period and displacement.
+pub fn find_velocity(
+    cells: impl IntoIterator<Item = (i64, i64)>,
+    rule: &Rule,
+    max_period: u64,
+) -> Option<Velocity> {
+    let mut plane = SparseGrid::new();
+    for (x, y) in cells {
+        plane.set(x, y, Cell::Alive);
+    }
+    let (shape, (left, top)) = canonical_form(plane.live_cells());
+    if shape.cells.is_empty() {
+        return None;
+    }
+
+    for period in 1..=max_period {
+        plane = next_generation_unbounded(&plane, rule);
+        let (next_shape, (next_left, next_top)) = canonical_form(plane.live_cells());
+        if next_shape == shape {
+            return Some(Velocity {
+                period,
+                dx: next_left - left,
+                dy: next_top - top,
+            });
+        }
+    }
+    None
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+#[path = "spaceship_test.rs"]
+#[cfg(test)]
+mod spaceship_test;
@@ -0,0 +1,91 @@
+use super::{canonical_form, find_velocity, Velocity};
+use crate::domain::patterns::PATTERNS;
+use crate::domain::rule::Rule;
+
+const LWSS: [(i64, i64); 9] = [
+    (1, 0),
+    (4, 0),
+    (0, 1),
+    (0, 2),
+    (4, 2),
+    (0, 3),
+    (1, 3),
+    (2, 3),
+    (3, 3),
+];
+
+fn glider() -> impl DoubleEndedIterator<Item = (i64, i64)> {
+    PATTERNS[0].cells.iter().map(|&(x, y)| (x as i64, y as i64))
+}
+
+#[test]
+fn canonical_form_ignores_position_and_order() {
+    let (shape, origin) = canonical_form(glider());
+    let (shifted, shifted_origin) = canonical_form(glider().rev().map(|(x, y)| (x - 7, y + 100)));
+
+    assert_eq!(shape, shifted);
+    assert_eq!(origin, (0, 0));
+    assert_eq!(shifted_origin, (-7, 100));
+    assert_eq!(shape.cells, vec![(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]);
+}
+
+#[test]
+fn glider_is_a_c4_diagonal_spaceship() {
+    let velocity = find_velocity(glider(), &Rule::conway(), 10).unwrap();
+
+    assert_eq!(
+        velocity,
+        Velocity {
+            period: 4,
+            dx: 1,
+            dy: 1
+        }
+    );
+    assert!(velocity.is_moving());
+    assert_eq!(velocity.to_string(), "c/4 diagonal");
+}
+
+#[test]
+fn lwss_is_a_c2_orthogonal_spaceship() {
+    let velocity = find_velocity(LWSS, &Rule::conway(), 10).unwrap();
+
+    assert_eq!((velocity.period, velocity.dx, velocity.dy), (4, -2, 0));
+    assert_eq!(velocity.to_string(), "c/2 orthogonal");
+}
+
+#[test]
+fn oscillators_and_still_lifes_do_not_move() {
+    let blinker = PATTERNS[1].cells.iter().map(|&(x, y)| (x as i64, y as i64));
+    let velocity = find_velocity(blinker, &Rule::conway(), 10).unwrap();
+    assert_eq!(velocity.period, 2);
+    assert!(!velocity.is_moving());
+
+    let block = [(0, 0), (1, 0), (0, 1), (1, 1)];
+    assert_eq!(
+        find_velocity(block, &Rule::conway(), 10),
+        Some(Velocity {
+            period: 1,
+            dx: 0,
+            dy: 0
+        })
+    );
+}
+
+#[test]
+fn find_velocity_gives_up_on_non_repeating_patterns() {
+    let r_pentomino = [(1, 0), (2, 0), (0, 1), (1, 1), (1, 2)];
+    assert_eq!(find_velocity(r_pentomino, &Rule::conway(), 50), None);
+    assert_eq!(find_velocity([], &Rule::conway(), 50), None);
+}
+
+#[test]
+fn speeds_use_conventional_notation() {
+    let speed = |period, dx, dy| Velocity { period, dx, dy }.to_string();
+
+    assert_eq!(speed(4, 0, -2), "c/2 orthogonal");
+    assert_eq!(speed(2, 2, 0), "c orthogonal");
+    assert_eq!(speed(5, 2, 0), "2c/5 orthogonal");
+    assert_eq!(speed(12, 3, 3), "c/4 diagonal");
+    assert_eq!(speed(6, -2, 1), "(2,1)c/6 oblique");
+    assert_eq!(speed(3, 0, 0), "stationary (period 3)");
+}
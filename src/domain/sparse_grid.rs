@@ -0,0 +1,61 @@
+use std::collections::HashSet;
+
+use super::board::{Board, Bounds};
+use super::cell::Cell;
+
+/// Unbounded plane that stores only the coordinates of live cells.
+#[derive(Clone, Default)]
+pub struct SparseGrid {
+    alive: HashSet<(i64, i64)>,
+}
+
+impl SparseGrid {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Copies every live cell of `board` onto a new plane.
+    pub fn from_board(board: &dyn Board) -> Self {
+        Self {
+            alive: board.live_cells().collect(),
+        }
+    }
+}
+
+impl Board for SparseGrid {
+    fn get(&self, x: i64, y: i64) -> Cell {
+        if self.alive.contains(&(x, y)) {
+            Cell::Alive
+        } else {
+            Cell::Dead
+        }
+    }
+
+    fn set(&mut self, x: i64, y: i64, cell: Cell) {
+        if cell.is_alive() {
+            self.alive.insert((x, y));
+        } else {
+            self.alive.remove(&(x, y));
+        }
+    }
+
+    fn clear(&mut self) {
+        self.alive.clear();
+    }
+
+    fn live_cells(&self) -> Box<dyn Iterator<Item = (i64, i64)> + '_> {
+        Box::new(self.alive.iter().copied())
+    }
+
+    fn extent(&self) -> Option<Bounds> {
+        None
+    }
+
+    fn population(&self) -> usize {
+        self.alive.len()
+    }
+}
+
+#[path = "sparse_grid_test.rs"]
+#[cfg(test)]
+mod sparse_grid_test;
@@ -0,0 +1,39 @@
+use super::SparseGrid;
+use crate::domain::board::Board;
+use crate::domain::cell::Cell;
+use crate::domain::grid::Grid;
+
+#[test]
+fn new_plane_is_empty() {
+    let plane = SparseGrid::new();
+    assert_eq!(plane.population(), 0);
+    assert_eq!(plane.get(0, 0), Cell::Dead);
+    assert_eq!(plane.extent(), None);
+}
+
+#[test]
+fn set_and_get_accept_any_coordinate() {
+    let mut plane = SparseGrid::new();
+    plane.set(-1_000_000, 5, Cell::Alive);
+    plane.set(i64::MAX, i64::MIN, Cell::Alive);
+
+    assert_eq!(plane.get(-1_000_000, 5), Cell::Alive);
+    assert_eq!(plane.get(i64::MAX, i64::MIN), Cell::Alive);
+    assert_eq!(plane.population(), 2);
+
+    plane.set(-1_000_000, 5, Cell::Dead);
+    assert_eq!(plane.get(-1_000_000, 5), Cell::Dead);
+    assert_eq!(plane.population(), 1);
+}
+
+#[test]
+fn from_board_copies_live_cells() {
+    let mut grid = Grid::new(4, 4);
+    grid.set(1, 2, Cell::Alive);
+    grid.set(3, 0, Cell::Alive);
+
+    let plane = SparseGrid::from_board(&grid);
+    let mut cells: Vec<_> = plane.live_cells().collect();
+    cells.sort();
+    assert_eq!(cells, vec![(1, 2), (3, 0)]);
+}
@@ -0,0 +1,78 @@
+use super::cell::Cell;
+
+/// How the edges of a finite grid are glued together when counting neighbors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Topology {
+    /// Everything outside the grid is dead.
+    #[default]
+    Bounded,
+    /// Left/right and top/bottom edges wrap around.
+    Torus,
+    /// Left/right edges wrap; crossing the top/bottom edge also mirrors x.
+    KleinBottle,
+    /// Crossing the left/right edge mirrors y, crossing the top/bottom edge mirrors x.
+    CrossSurface,
+    /// Everything outside the grid is permanently alive.
+    AliveBorder,
+}
+
+pub const TOPOLOGIES: [Topology; 5] = [
+    Topology::Bounded,
+    Topology::Torus,
+    Topology::KleinBottle,
+    Topology::CrossSurface,
+    Topology::AliveBorder,
+];
+
+impl Topology {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Topology::Bounded => "Bounded",
+            Topology::Torus => "Torus",
+            Topology::KleinBottle => "Klein bottle",
+            Topology::CrossSurface => "Cross-surface",
+            Topology::AliveBorder => "Alive border",
+        }
+    }
+
+    /// Maps a possibly out-of-range coordinate onto the grid cell it refers to,
+    /// or `None` when the coordinate lies off a non-wrapping edge.
+    pub fn wrap(&self, x: i32, y: i32, width: usize, height: usize) -> Option<(usize, usize)> {
+        let w = width as i32;
+        let h = height as i32;
+        let x_out = x < 0 || x >= w;
+        let y_out = y < 0 || y >= h;
+
+        let (x, y) = match self {
+            Topology::Bounded | Topology::AliveBorder => {
+                if x_out || y_out {
+                    return None;
+                }
+                (x, y)
+            }
+            Topology::Torus => (x.rem_euclid(w), y.rem_euclid(h)),
+            Topology::KleinBottle => {
+                let wrapped_x = x.rem_euclid(w);
+                let x = if y_out { w - 1 - wrapped_x } else { wrapped_x };
+                (x, y.rem_euclid(h))
+            }
+            Topology::CrossSurface => {
+                let wrapped_x = x.rem_euclid(w);
+                let wrapped_y = y.rem_euclid(h);
+                let x = if y_out { w - 1 - wrapped_x } else { wrapped_x };
+                let y = if x_out { h - 1 - wrapped_y } else { wrapped_y };
+                (x, y)
+            }
+        };
+
+        Some((x as usize, y as usize))
+    }
+
+    /// State assumed for neighbors that fall off a non-wrapping edge.
+    pub fn outside_cell(&self) -> Cell {
+        match self {
+            Topology::AliveBorder => Cell::Alive,
+            _ => Cell::Dead,
+        }
+    }
+}
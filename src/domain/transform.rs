@@ -0,0 +1,63 @@
+/// Rotation or reflection applied to a pattern before it is placed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transform {
+    RotateClockwise,
+    RotateAnticlockwise,
+    Rotate180,
+    /// Mirrors left to right.
+    FlipHorizontal,
+    /// Mirrors top to bottom.
+    FlipVertical,
+}
+
+pub const TRANSFORMS: [Transform; 5] = [
+    Transform::RotateAnticlockwise,
+    Transform::RotateClockwise,
+    Transform::Rotate180,
+    Transform::FlipHorizontal,
+    Transform::FlipVertical,
+];
+
+impl Transform {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Transform::RotateClockwise => "Rotate 90° ⟳",
+            Transform::RotateAnticlockwise => "Rotate 90° ⟲",
+            Transform::Rotate180 => "Rotate 180°",
+            Transform::FlipHorizontal => "Flip ⇆",
+            Transform::FlipVertical => "Flip ⇅",
+        }
+    }
+
+    /// Whether width and height trade places.
+    pub fn swaps_axes(&self) -> bool {
+        matches!(
+            self,
+            Transform::RotateClockwise | Transform::RotateAnticlockwise
+        )
+    }
+
+    /// Moves an offset about the origin, with y pointing down the screen.
+    pub fn apply(&self, (x, y): (i64, i64)) -> (i64, i64) {
+        match self {
+            Transform::RotateClockwise => (-y, x),
+            Transform::RotateAnticlockwise => (y, -x),
+            Transform::Rotate180 => (-x, -y),
+            Transform::FlipHorizontal => (-x, y),
+            Transform::FlipVertical => (x, -y),
+        }
+    }
+
+    /// Transforms pattern offsets in place about the origin, so a pattern
+    /// centred on `(0, 0)` stays centred.
+    pub fn apply_to_offsets(&self, cells: &mut [(i32, i32)]) {
+        for cell in cells {
+            let (x, y) = self.apply((cell.0 as i64, cell.1 as i64));
+            *cell = (x as i32, y as i32);
+        }
+    }
+}
+
+#[path = "transform_test.rs"]
+#[cfg(test)]
+mod transform_test;
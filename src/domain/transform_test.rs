@@ -0,0 +1,101 @@
+use super::{Transform, TRANSFORMS};
+use crate::domain::clip::Clip;
+use crate::domain::rule::Rule;
+use crate::domain::spaceship::find_velocity;
+
+fn glider() -> Clip {
+    Clip {
+        width: 3,
+        height: 3,
+        cells: vec![(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)],
+    }
+}
+
+fn heading(clip: &Clip) -> (i64, i64) {
+    let velocity = find_velocity(clip.cells.iter().copied(), &Rule::conway(), 10).unwrap();
+    (velocity.dx, velocity.dy)
+}
+
+#[test]
+fn transforms_turn_the_glider() {
+    let glider = glider();
+    assert_eq!(heading(&glider), (1, 1));
+
+    let cases = [
+        (Transform::RotateClockwise, (-1, 1)),
+        (Transform::RotateAnticlockwise, (1, -1)),
+        (Transform::Rotate180, (-1, -1)),
+        (Transform::FlipHorizontal, (-1, 1)),
+        (Transform::FlipVertical, (1, -1)),
+    ];
+    for (transform, expected) in cases {
+        assert_eq!(
+            heading(&glider.transformed(transform)),
+            expected,
+            "{}",
+            transform.name()
+        );
+    }
+}
+
+#[test]
+fn rotation_swaps_the_clip_size_and_keeps_cells_inside() {
+    let clip = Clip {
+        width: 4,
+        height: 2,
+        cells: vec![(0, 0), (3, 1)],
+    };
+
+    for transform in TRANSFORMS {
+        let turned = clip.transformed(transform);
+        let expected = if transform.swaps_axes() {
+            (2, 4)
+        } else {
+            (4, 2)
+        };
+        assert_eq!((turned.width, turned.height), expected);
+        assert!(turned
+            .cells
+            .iter()
+            .all(|&(x, y)| (0..turned.width).contains(&x) && (0..turned.height).contains(&y)));
+    }
+    assert_eq!(
+        clip.transformed(Transform::RotateClockwise).cells,
+        vec![(1, 0), (0, 3)]
+    );
+}
+
+#[test]
+fn transforms_compose_back_to_the_original() {
+    let glider = glider();
+    let once = |clip: &Clip, transform| clip.transformed(transform);
+
+    let mut turned = glider.clone();
+    for _ in 0..4 {
+        turned = once(&turned, Transform::RotateClockwise);
+    }
+    assert_eq!(turned, glider);
+    assert_eq!(
+        once(
+            &once(&glider, Transform::RotateClockwise),
+            Transform::RotateAnticlockwise
+        ),
+        glider
+    );
+    assert_eq!(
+        once(
+            &once(&glider, Transform::FlipHorizontal),
+            Transform::FlipVertical
+        ),
+        once(&glider, Transform::Rotate180)
+    );
+}
+
+#[test]
+fn offsets_turn_about_the_origin() {
+    let mut cells = [(1, 0), (-2, 3)];
+
+    Transform::RotateClockwise.apply_to_offsets(&mut cells);
+
+    assert_eq!(cells, [(0, 1), (-3, -2)]);
+}
@@ -1,10 +1,36 @@
-mod application;
-mod domain;
+use std::env;
+use std::process::ExitCode;
 
-use application::GameApp;
 use eframe::egui::ViewportBuilder;
+use game_of_life::application::{cli, search, tui, GameApp};
 
-fn main() -> eframe::Result<()> {
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("run") => return cli::main(&args[1..]),
+        Some("search") => return search::main(&args[1..]),
+        _ => {}
+    }
+    if args.iter().any(|arg| arg == "--tui") {
+        return match tui::run() {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(err) => {
+                eprintln!("error: {err}");
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    match run_gui() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run_gui() -> eframe::Result<()> {
     let viewport = ViewportBuilder::default()
         .with_inner_size([1200.0, 800.0])
         .with_title("Conway's Game of Life");